@@ -4,7 +4,7 @@
 # Automatically sets up entities during import based on their identifier
 
 # Entities that require room_coords to be set (use LDtk identifier in PascalCase)
-const ENTITIES_WITH_ROOM_COORDS := ["Checkpoint", "PlainKey", "PlainLock", "Portal", "SwitchDoor", "CollectibleStar"]
+const ENTITIES_WITH_ROOM_COORDS := ["Checkpoint", "PlainKey", "PlainLock", "Portal", "SwitchDoor", "CollectibleStar", "PushableCrate"]
 
 # Entities that use IID-based naming (EntityIdentifier_IID8)
 const ENTITIES_WITH_IID_NAME := ["SwitchDoor"]
@@ -36,6 +36,8 @@ func post_import(entity_layer: LDTKEntityLayer) -> LDTKEntityLayer:
 				setup_portal(entity_layer, entity, entity_counts[entity_key])
 			"pressure_plate":
 				setup_pressure_plate(entity_layer, entity, entity_counts[entity_key])
+			"ability_pickup":
+				setup_ability_pickup(entity_layer, entity, entity_counts[entity_key])
 			_:
 				setup_generic_entity(entity_layer, entity, entity_counts[entity_key])
 
@@ -365,6 +367,36 @@ func setup_pressure_plate(entity_layer: LDTKEntityLayer, entity_data: Variant, s
 		var target_path := NodePath("../%s" % target_node_name)
 		instance.set("target_id", target_path)
 
+	# Optional: latched plates keep their door open across visits
+	var latched: bool = get_entity_field(entity_data, "latched", false)
+	instance.set("latched", latched)
+
+	finalize_entity(entity_layer, instance, entity_data, entity_key)
+	print("  - Instantiated %s.tscn" % entity_key)
+	print("  - Configured: target_room=(%d, %d), target_id=%s, latched=%s" % [target_room_x, target_room_y, target_iid, latched])
+
+
+func setup_ability_pickup(entity_layer: LDTKEntityLayer, entity_data: Variant, sequence: int) -> void:
+	"""Set up an AbilityPickup entity with the ability it grants from LDtk fields"""
+	var entity_key := "ability_pickup"
+	var scene_path := get_scene_path(entity_key)
+
+	print("Setting up %s" % get_entity_identifier(entity_data))
+
+	var instance := instantiate_entity(entity_layer, entity_data, scene_path, sequence)
+	if not instance:
+		return
+
+	var room_coords: Variant = get_room_coords(entity_layer)
+	if room_coords != null:
+		instance.set("room_coords", room_coords)
+	else:
+		printerr("ability_pickup room coords could not be resolved for layer: %s" % entity_layer.name)
+
+	# Ability name as used in save files, e.g. "double_jump" or "swim_dive"
+	var ability: String = get_entity_field(entity_data, "ability", "double_jump")
+	instance.set("ability", ability)
+
 	finalize_entity(entity_layer, instance, entity_data, entity_key)
 	print("  - Instantiated %s.tscn" % entity_key)
-	print("  - Configured: target_room=(%d, %d), target_id=%s" % [target_room_x, target_room_y, target_iid])
+	print("  - Configured: ability=%s" % ability)
@@ -0,0 +1,13 @@
+extends SceneTree
+
+const PLAYER_SCENE := preload("res://player/player.tscn")
+
+
+# Run through `cargo xtask jump-reach`; prints the reachability table of the
+# player's movement tuning.
+func _initialize() -> void:
+	var player := PLAYER_SCENE.instantiate()
+	root.add_child(player)
+	print(player.get_jump_reach_report())
+	player.free()
+	quit()
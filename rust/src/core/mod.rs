@@ -1,4 +1,5 @@
 pub mod player;
 pub mod progress;
 pub mod session;
+pub mod settings;
 pub mod world;
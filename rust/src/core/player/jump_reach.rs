@@ -0,0 +1,306 @@
+//! Reachability numbers for a `MovementConfig`, measured by stepping
+//! `PlayerMovement::physics_process` over flat ground at a fixed timestep,
+//! so level design numbers follow the movement code instead of a formula.
+//!
+//! Every measurement is `None` when the config cannot start a jump from the
+//! floor, e.g. with no jump buffer time.
+
+use std::fmt;
+
+use godot::prelude::*;
+
+use super::{MovementConfig, MovementInput, PlayerMovement};
+
+/// Matches the project's default 60 Hz physics tick.
+pub const TIMESTEP: f64 = 1.0 / 60.0;
+/// A jump still airborne after this many steps never lands.
+const MAX_STEPS: u32 = 60 * 10;
+/// Steps on the floor before the jump; the first one lets the movement
+/// notice it is grounded.
+const RUN_UP_STEPS: u32 = 2;
+
+/// Distances are in pixels and times in seconds; a jump that never lands
+/// reports an infinite distance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JumpReach {
+    /// Peak height of a held jump above the takeoff point.
+    pub max_height: f32,
+    /// Time from takeoff until a held jump stops rising.
+    pub apex_time: f32,
+    /// Horizontal distance from takeoff to landing at the same height, at
+    /// full walk speed with jump held throughout.
+    pub full_jump_distance: f32,
+    /// The same for a jump released one step after takeoff.
+    pub cut_jump_distance: f32,
+    /// Distance from a ledge to a landing at the ledge's height when the
+    /// held jump is pressed as late as coyote time allows.
+    pub coyote_jump_distance: f32,
+}
+
+impl JumpReach {
+    pub const METRIC_NAMES: [&'static str; 5] = [
+        "max_height",
+        "apex_time",
+        "full_jump_distance",
+        "cut_jump_distance",
+        "coyote_jump_distance",
+    ];
+
+    pub fn metric(&self, name: &str) -> Option<f32> {
+        match name {
+            "max_height" => Some(self.max_height),
+            "apex_time" => Some(self.apex_time),
+            "full_jump_distance" => Some(self.full_jump_distance),
+            "cut_jump_distance" => Some(self.cut_jump_distance),
+            "coyote_jump_distance" => Some(self.coyote_jump_distance),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JumpReach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "max height     {:>8.1} px", self.max_height)?;
+        writeln!(f, "apex time      {:>8.3} s", self.apex_time)?;
+        writeln!(f, "full jump      {:>8.1} px", self.full_jump_distance)?;
+        writeln!(f, "cut jump       {:>8.1} px", self.cut_jump_distance)?;
+        write!(f, "coyote jump    {:>8.1} px", self.coyote_jump_distance)
+    }
+}
+
+pub fn jump_reach(config: &MovementConfig) -> Option<JumpReach> {
+    let full = full_jump(config)?;
+    Some(JumpReach {
+        max_height: full.max_height,
+        apex_time: full.apex_time,
+        full_jump_distance: full.distance_from(full.takeoff_x),
+        cut_jump_distance: cut_jump_distance(config)?,
+        coyote_jump_distance: coyote_jump_distance(config)?,
+    })
+}
+
+pub fn max_jump_height(config: &MovementConfig) -> Option<f32> {
+    full_jump(config).map(|flight| flight.max_height)
+}
+
+pub fn apex_time(config: &MovementConfig) -> Option<f32> {
+    full_jump(config).map(|flight| flight.apex_time)
+}
+
+pub fn full_jump_distance(config: &MovementConfig) -> Option<f32> {
+    full_jump(config).map(|flight| flight.distance_from(flight.takeoff_x))
+}
+
+pub fn cut_jump_distance(config: &MovementConfig) -> Option<f32> {
+    let flight = fly(
+        config,
+        JumpScript {
+            press_step: RUN_UP_STEPS,
+            release_step: Some(RUN_UP_STEPS + 1),
+            ledge_x: None,
+        },
+    )?;
+    Some(flight.distance_from(flight.takeoff_x))
+}
+
+/// Tries ever later presses after running off a ledge and keeps the last one
+/// that still jumped.
+pub fn coyote_jump_distance(config: &MovementConfig) -> Option<f32> {
+    let ledge_x = 0.0;
+    let mut latest = None;
+    for press_step in RUN_UP_STEPS.. {
+        let script = JumpScript {
+            press_step,
+            release_step: None,
+            ledge_x: Some(ledge_x),
+        };
+        match fly(config, script) {
+            Some(flight) => latest = Some(flight),
+            None => break,
+        }
+    }
+    latest.map(|flight| flight.distance_from(ledge_x))
+}
+
+fn full_jump(config: &MovementConfig) -> Option<Flight> {
+    fly(
+        config,
+        JumpScript {
+            press_step: RUN_UP_STEPS,
+            release_step: None,
+            ledge_x: None,
+        },
+    )
+}
+
+struct JumpScript {
+    press_step: u32,
+    release_step: Option<u32>,
+    /// The floor ends at this x until the jump starts; beyond it the body
+    /// falls. `None` is endless floor.
+    ledge_x: Option<f32>,
+}
+
+struct Flight {
+    takeoff_x: f32,
+    max_height: f32,
+    apex_time: f32,
+    landing_x: Option<f32>,
+}
+
+impl Flight {
+    fn distance_from(&self, x: f32) -> f32 {
+        self.landing_x
+            .map_or(f32::INFINITY, |landing_x| landing_x - x)
+    }
+}
+
+/// Runs right at walk speed and presses jump on `press_step`, returning
+/// `None` when that press does not start a jump. Once airborne, the ground
+/// at y = 0 is solid everywhere, so the flight lands at takeoff height.
+fn fly(config: &MovementConfig, script: JumpScript) -> Option<Flight> {
+    let mut movement = PlayerMovement::new(config.clone());
+    // A late press must not turn into an air jump.
+    movement.abilities.double_jump = false;
+
+    let step_seconds = TIMESTEP as f32;
+    let start_x = script.ledge_x.map_or(0.0, |ledge_x| {
+        ledge_x - config.walk_speed * step_seconds * 2.5
+    });
+    let mut position = Vector2::new(start_x, 0.0);
+    let mut velocity = Vector2::new(config.walk_speed, 0.0);
+    let mut is_on_floor = true;
+    let mut takeoff: Option<(u32, Vector2)> = None;
+    let mut max_height = 0.0_f32;
+    let mut apex_time = None;
+
+    for step in 0..MAX_STEPS {
+        let released = script.release_step.is_some_and(|release| step >= release);
+        let input = MovementInput {
+            direction: 1.0,
+            jump_just_pressed: step == script.press_step,
+            jump_just_released: script.release_step == Some(step),
+            jump_held: step >= script.press_step && !released,
+            ..Default::default()
+        };
+        velocity = movement.physics_process(velocity, is_on_floor, TIMESTEP, input);
+        if step == script.press_step {
+            if !movement.jumped_this_frame() {
+                return None;
+            }
+            takeoff = Some((step, position));
+        }
+
+        position += velocity * step_seconds;
+        let floor_below =
+            takeoff.is_some() || script.ledge_x.is_none_or(|ledge_x| position.x <= ledge_x);
+        is_on_floor = floor_below && position.y >= 0.0 && velocity.y >= 0.0;
+        if is_on_floor {
+            position.y = 0.0;
+            velocity.y = 0.0;
+        }
+        movement.post_physics_update(is_on_floor);
+
+        let Some((takeoff_step, takeoff_position)) = takeoff else {
+            continue;
+        };
+        max_height = max_height.max(takeoff_position.y - position.y);
+        // The body peaked at the end of the previous step.
+        if apex_time.is_none() && velocity.y >= 0.0 {
+            apex_time = Some((step - takeoff_step) as f32 * step_seconds);
+        }
+        if is_on_floor {
+            return Some(Flight {
+                takeoff_x: takeoff_position.x,
+                max_height,
+                apex_time: apex_time.unwrap_or(0.0),
+                landing_x: Some(position.x),
+            });
+        }
+    }
+
+    takeoff.map(|(_, takeoff_position)| Flight {
+        takeoff_x: takeoff_position.x,
+        max_height,
+        apex_time: apex_time.unwrap_or(f32::INFINITY),
+        landing_x: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_jumps_reach_higher_and_further_than_cut_jumps() {
+        let config = MovementConfig::default();
+        let reach = jump_reach(&config).unwrap();
+
+        assert!(reach.max_height > 0.0);
+        assert!(reach.apex_time > 0.0);
+        assert!(reach.full_jump_distance > reach.cut_jump_distance);
+        assert!(reach.cut_jump_distance > 0.0);
+        assert_eq!(Some(reach.max_height), max_jump_height(&config));
+        assert_eq!(Some(reach.full_jump_distance), full_jump_distance(&config));
+    }
+
+    #[test]
+    fn constant_gravity_matches_projectile_motion() {
+        let config = MovementConfig {
+            apex_gravity_multiplier: 1.0,
+            fall_gravity_multiplier: 1.0,
+            max_fall_speed: f32::INFINITY,
+            ..MovementConfig::platformer(1000.0)
+        };
+        let rise_seconds = -config.jump_velocity / config.gravity;
+        let height = config.jump_velocity * config.jump_velocity / (2.0 * config.gravity);
+        let step = TIMESTEP as f32;
+
+        let reach = jump_reach(&config).unwrap();
+
+        assert!((reach.apex_time - rise_seconds).abs() <= step);
+        assert!((reach.max_height - height).abs() <= -config.jump_velocity * step);
+        let distance = config.walk_speed * 2.0 * rise_seconds;
+        assert!((reach.full_jump_distance - distance).abs() <= config.walk_speed * 2.0 * step);
+    }
+
+    #[test]
+    fn coyote_time_extends_the_reach_from_a_ledge() {
+        let config = MovementConfig::default();
+        let without_coyote = MovementConfig {
+            coyote_time: 0.0,
+            ..MovementConfig::default()
+        };
+
+        let with_coyote = coyote_jump_distance(&config).unwrap();
+        let from_edge = coyote_jump_distance(&without_coyote).unwrap();
+        let full = full_jump_distance(&config).unwrap();
+
+        assert!(with_coyote > from_edge);
+        assert!(from_edge >= full - config.walk_speed * TIMESTEP as f32);
+    }
+
+    #[test]
+    fn configs_that_cannot_jump_have_no_reach() {
+        let config = MovementConfig {
+            jump_buffer_time: 0.0,
+            ..MovementConfig::default()
+        };
+
+        assert_eq!(jump_reach(&config), None);
+        assert_eq!(max_jump_height(&config), None);
+        assert_eq!(cut_jump_distance(&config), None);
+        assert_eq!(coyote_jump_distance(&config), None);
+    }
+
+    #[test]
+    fn looks_up_metrics_by_name() {
+        let reach = jump_reach(&MovementConfig::default()).unwrap();
+
+        for name in JumpReach::METRIC_NAMES {
+            assert!(reach.metric(name).is_some(), "{name}");
+        }
+        assert_eq!(reach.metric("apex_time"), Some(reach.apex_time));
+        assert_eq!(reach.metric("wall_jump_distance"), None);
+    }
+}
@@ -0,0 +1,1790 @@
+use godot::prelude::*;
+
+pub mod jump_reach;
+pub mod profile;
+
+const INPUT_DEADZONE: f32 = 0.01;
+/// How far down the stick must be held to fast-fall, so a loose diagonal
+/// does not trigger it.
+const FAST_FALL_INPUT_THRESHOLD: f32 = 0.5;
+/// How far up or down the stick must be held to mantle or drop from a ledge.
+const LEDGE_INPUT_THRESHOLD: f32 = 0.5;
+/// How far down the stick must be held to crouch.
+const CROUCH_INPUT_THRESHOLD: f32 = 0.5;
+const DEFAULT_GRAVITY: f32 = 980.0;
+/// Keeps a slope at exactly the maximum floor angle walkable despite
+/// rounding in collision normals.
+const FLOOR_ANGLE_EPSILON: f32 = 0.001;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementState {
+    Air,
+    Floor,
+    /// Airborne, pressing into a wall, with the fall speed capped.
+    WallSlide,
+    Dash,
+    /// Hanging from a ledge corner, waiting to mantle or drop.
+    LedgeHang,
+    /// On the floor with the shorter hitbox, walking slower.
+    Crouch,
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct MovementInput {
+    pub direction: f32,
+    pub vertical_direction: f32,
+    pub jump_just_pressed: bool,
+    pub jump_just_released: bool,
+    pub jump_held: bool,
+    /// Side of the wall the body touches: -1.0 left, 1.0 right, 0.0 none.
+    pub wall_direction: f32,
+    pub dash_just_pressed: bool,
+    /// Direction to dash in; need not be normalized.
+    pub dash_direction: Vector2,
+    /// Normal of the floor under the body; zero when airborne or unknown,
+    /// which counts as flat ground.
+    pub floor_normal: Vector2,
+    /// Velocity of the moving platform the body stands on; zero on static
+    /// ground or in the air.
+    pub platform_velocity: Vector2,
+    /// Side of a grabbable ledge corner level with the body's top: -1.0
+    /// left, 1.0 right, 0.0 none.
+    pub ledge_direction: f32,
+    /// Whether holding down may crouch; false on platforms that down drops
+    /// through instead.
+    pub can_crouch: bool,
+    /// Whether a ceiling over the crouched body leaves no room to stand.
+    pub ceiling_blocks_standing: bool,
+}
+
+/// Behaviours that stay off until the player unlocks them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MovementAbilities {
+    pub double_jump: bool,
+    pub dash: bool,
+    pub wall_jump: bool,
+}
+
+impl MovementAbilities {
+    pub const ALL: Self = Self {
+        double_jump: true,
+        dash: true,
+        wall_jump: true,
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MovementConfig {
+    pub gravity: f32,
+    pub walk_speed: f32,
+    pub ground_accel_speed: f32,
+    pub ground_decel_speed: f32,
+    pub air_accel_speed: f32,
+    pub air_decel_speed: f32,
+    pub turn_accel_multiplier: f32,
+    pub jump_velocity: f32,
+    pub jump_buffer_time: f32,
+    pub coyote_time: f32,
+    /// Extra jumps allowed between landings once double jump is unlocked.
+    pub air_jumps: u32,
+    pub jump_release_velocity_factor: f32,
+    /// Gravity scale near the top of a held jump, while `|vy|` is below
+    /// `apex_velocity_threshold`.
+    pub apex_gravity_multiplier: f32,
+    pub apex_velocity_threshold: f32,
+    /// Gravity scale while falling.
+    pub fall_gravity_multiplier: f32,
+    pub max_fall_speed: f32,
+    /// Gravity scale and speed cap while falling with down held.
+    pub fast_fall_gravity_multiplier: f32,
+    pub fast_fall_max_speed: f32,
+    pub min_walk_speed: f32,
+    /// Steepest slope, in degrees, that counts as floor rather than wall.
+    pub floor_max_angle_degrees: f32,
+    /// How far down the body snaps to the floor each step, so walking
+    /// downhill or over a slope's crest does not hop.
+    pub floor_snap_length: f32,
+    /// How far a floor jump turns from straight up toward the slope normal:
+    /// 0.0 ignores the slope and 1.0 leaves along the normal. The vertical
+    /// speed stays `jump_velocity`, so jumps reach the same height on any
+    /// slope; the default keeps a jump up a 45° slope moving uphill.
+    pub slope_jump_normal_blend: f32,
+    /// Cap on the platform velocity carried into a jump or a walk-off.
+    pub max_inherited_platform_speed: f32,
+    pub climb_speed: f32,
+    pub wall_slide_max_speed: f32,
+    /// Horizontal speed away from the wall given by a wall jump.
+    pub wall_jump_kick_speed: f32,
+    /// How long after a wall jump horizontal input is ignored, so the kick
+    /// carries the player clear of the wall.
+    pub wall_jump_lockout_time: f32,
+    pub wall_coyote_time: f32,
+    /// How long after dropping from a ledge it cannot be grabbed again.
+    pub ledge_regrab_time: f32,
+    /// Fraction of `walk_speed` kept while crouching.
+    pub crouch_speed_multiplier: f32,
+    pub dash_speed: f32,
+    pub dash_duration: f32,
+    /// Time after a dash ends before the next one can start.
+    pub dash_cooldown: f32,
+    pub dash_buffer_time: f32,
+    /// Dashes allowed between landings; dashes from the floor are free.
+    pub air_dash_charges: u32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self::platformer(DEFAULT_GRAVITY)
+    }
+}
+
+impl MovementConfig {
+    pub fn platformer(gravity: f32) -> Self {
+        Self {
+            gravity,
+            walk_speed: 120.0,
+            ground_accel_speed: 720.0,
+            ground_decel_speed: 1080.0,
+            air_accel_speed: 540.0,
+            air_decel_speed: 720.0,
+            turn_accel_multiplier: 1.4,
+            jump_velocity: -320.0,
+            jump_buffer_time: 0.12,
+            coyote_time: 0.10,
+            air_jumps: 1,
+            jump_release_velocity_factor: 0.5,
+            apex_gravity_multiplier: 0.5,
+            apex_velocity_threshold: 40.0,
+            fall_gravity_multiplier: 1.5,
+            max_fall_speed: 400.0,
+            fast_fall_gravity_multiplier: 2.5,
+            fast_fall_max_speed: 560.0,
+            min_walk_speed: 0.1,
+            floor_max_angle_degrees: 46.0,
+            floor_snap_length: 8.0,
+            slope_jump_normal_blend: 0.3,
+            max_inherited_platform_speed: 240.0,
+            climb_speed: 80.0,
+            wall_slide_max_speed: 60.0,
+            wall_jump_kick_speed: 150.0,
+            wall_jump_lockout_time: 0.15,
+            wall_coyote_time: 0.10,
+            ledge_regrab_time: 0.25,
+            crouch_speed_multiplier: 0.5,
+            dash_speed: 300.0,
+            dash_duration: 0.15,
+            dash_cooldown: 0.25,
+            dash_buffer_time: 0.12,
+            air_dash_charges: 1,
+        }
+    }
+
+    pub fn floor_max_angle(&self) -> f32 {
+        self.floor_max_angle_degrees.to_radians()
+    }
+}
+
+pub struct PlayerMovement {
+    pub state: MovementState,
+    pub config: MovementConfig,
+    /// Everything is enabled until the owner narrows it, e.g. to the
+    /// abilities unlocked in the active save slot.
+    pub abilities: MovementAbilities,
+    jump_buffer_timer: f32,
+    coyote_timer: f32,
+    was_on_floor: bool,
+    jumped_this_frame: bool,
+    buffered_jump_cut_requested: bool,
+    wall_coyote_timer: f32,
+    wall_jump_lockout_timer: f32,
+    on_wall: bool,
+    /// Side of the last wall touched, kept for wall coyote jumps.
+    wall_side: f32,
+    wall_sliding: bool,
+    dash_timer: f32,
+    dash_cooldown_timer: f32,
+    dash_buffer_timer: f32,
+    buffered_dash_direction: Vector2,
+    dash_velocity: Vector2,
+    air_dash_charges: u32,
+    air_jumps: u32,
+    /// Platform velocity from the last frame on the floor.
+    platform_velocity: Vector2,
+    /// Set when the body walked off a platform; its velocity is added on
+    /// the next frame.
+    platform_launch_pending: bool,
+    /// Side of the ledge being hung from.
+    ledge_side: Option<f32>,
+    ledge_regrab_timer: f32,
+    mantled_this_frame: bool,
+    crouching: bool,
+}
+
+impl PlayerMovement {
+    pub fn new(config: MovementConfig) -> Self {
+        Self {
+            state: MovementState::Air,
+            abilities: MovementAbilities::ALL,
+            air_dash_charges: config.air_dash_charges,
+            air_jumps: config.air_jumps,
+            config,
+            jump_buffer_timer: 0.0,
+            coyote_timer: 0.0,
+            was_on_floor: false,
+            jumped_this_frame: false,
+            buffered_jump_cut_requested: false,
+            wall_coyote_timer: 0.0,
+            wall_jump_lockout_timer: 0.0,
+            on_wall: false,
+            wall_side: 0.0,
+            wall_sliding: false,
+            dash_timer: 0.0,
+            dash_cooldown_timer: 0.0,
+            dash_buffer_timer: 0.0,
+            buffered_dash_direction: Vector2::ZERO,
+            dash_velocity: Vector2::ZERO,
+            platform_velocity: Vector2::ZERO,
+            platform_launch_pending: false,
+            ledge_side: None,
+            ledge_regrab_timer: 0.0,
+            mantled_this_frame: false,
+            crouching: false,
+        }
+    }
+
+    pub fn reset_transient_state(&mut self) {
+        self.state = MovementState::Air;
+        self.jump_buffer_timer = 0.0;
+        self.coyote_timer = 0.0;
+        self.was_on_floor = false;
+        self.jumped_this_frame = false;
+        self.buffered_jump_cut_requested = false;
+        self.wall_coyote_timer = 0.0;
+        self.wall_jump_lockout_timer = 0.0;
+        self.on_wall = false;
+        self.wall_side = 0.0;
+        self.wall_sliding = false;
+        self.dash_timer = 0.0;
+        self.dash_cooldown_timer = 0.0;
+        self.dash_buffer_timer = 0.0;
+        self.air_dash_charges = self.config.air_dash_charges;
+        self.air_jumps = self.config.air_jumps;
+        self.platform_velocity = Vector2::ZERO;
+        self.platform_launch_pending = false;
+        self.ledge_side = None;
+        self.ledge_regrab_timer = 0.0;
+        self.mantled_this_frame = false;
+        self.crouching = false;
+    }
+
+    pub fn physics_process(
+        &mut self,
+        velocity: Vector2,
+        is_on_floor: bool,
+        delta: f64,
+        input: MovementInput,
+    ) -> Vector2 {
+        let delta = delta as f32;
+        let mut new_velocity = velocity;
+
+        self.jumped_this_frame = false;
+        self.mantled_this_frame = false;
+        self.wall_sliding = false;
+        self.tick_timers(delta);
+        self.track_wall_contact(input.wall_direction, is_on_floor);
+
+        if self.ledge_side.is_some() {
+            return self.hang_from_ledge(input);
+        }
+        if self.try_grab_ledge(velocity, is_on_floor, input) {
+            return Vector2::ZERO;
+        }
+
+        if input.jump_just_pressed {
+            self.jump_buffer_timer = self.config.jump_buffer_time;
+            self.buffered_jump_cut_requested = false;
+        }
+
+        if input.jump_just_released && self.jump_buffer_timer > 0.0 && !self.can_jump(is_on_floor) {
+            self.buffered_jump_cut_requested = true;
+        }
+
+        if is_on_floor {
+            self.air_dash_charges = self.config.air_dash_charges;
+            self.air_jumps = self.config.air_jumps;
+            self.platform_velocity = input.platform_velocity;
+        } else if self.platform_launch_pending {
+            new_velocity += self.inherited_platform_velocity();
+        }
+        self.platform_launch_pending = false;
+        if input.dash_just_pressed {
+            self.dash_buffer_timer = self.config.dash_buffer_time;
+            self.buffered_dash_direction = input.dash_direction;
+        }
+        if self.is_dashing() || self.try_start_dash(is_on_floor) {
+            self.state = MovementState::Dash;
+            return self.dash_velocity;
+        }
+
+        self.update_crouch(is_on_floor, input);
+        self.apply_gravity(&mut new_velocity, delta, is_on_floor, input);
+        self.apply_walk(&mut new_velocity, delta, input.direction, is_on_floor);
+
+        if self.apply_jump(&mut new_velocity, is_on_floor, input.floor_normal)
+            || self.apply_wall_jump(&mut new_velocity, is_on_floor)
+            || self.apply_air_jump(&mut new_velocity, is_on_floor)
+        {
+            self.jumped_this_frame = true;
+        }
+
+        if input.jump_just_released || self.buffered_jump_cut_requested {
+            self.apply_jump_cut(&mut new_velocity);
+
+            if self.jumped_this_frame || self.jump_buffer_timer <= 0.0 {
+                self.buffered_jump_cut_requested = false;
+            }
+        }
+
+        if !self.jumped_this_frame {
+            self.apply_wall_slide(&mut new_velocity, input.direction);
+        }
+
+        self.state = if self.jumped_this_frame || !is_on_floor {
+            self.airborne_state()
+        } else {
+            self.floor_state()
+        };
+
+        new_velocity
+    }
+
+    /// Gravity and walking are suspended while this is true.
+    pub fn is_dashing(&self) -> bool {
+        self.dash_timer > 0.0
+    }
+
+    /// Whether the last `physics_process` call started a jump.
+    pub fn jumped_this_frame(&self) -> bool {
+        self.jumped_this_frame
+    }
+
+    /// Side of the ledge being hung from, if any.
+    pub fn ledge_side(&self) -> Option<f32> {
+        self.ledge_side
+    }
+
+    /// Whether the last `physics_process` call climbed off a ledge; the owner
+    /// then moves the body up onto it.
+    pub fn mantled_this_frame(&self) -> bool {
+        self.mantled_this_frame
+    }
+
+    /// Whether the body should use its crouching hitbox. Stays set off the
+    /// floor while a ceiling blocks standing, e.g. crawling off a step.
+    pub fn is_crouching(&self) -> bool {
+        self.crouching
+    }
+
+    pub fn post_physics_update(&mut self, is_on_floor: bool) {
+        if is_on_floor {
+            self.coyote_timer = 0.0;
+            if !self.jumped_this_frame {
+                self.state = self.floor_state();
+            }
+        } else {
+            if self.was_on_floor && !self.jumped_this_frame {
+                self.coyote_timer = self.config.coyote_time;
+                self.platform_launch_pending = true;
+            }
+            self.state = self.airborne_state();
+        }
+
+        if self.is_dashing() {
+            self.state = MovementState::Dash;
+        }
+        if self.ledge_side.is_some() {
+            self.state = MovementState::LedgeHang;
+        }
+
+        self.was_on_floor = is_on_floor;
+        self.jumped_this_frame = false;
+    }
+
+    fn tick_timers(&mut self, delta: f32) {
+        self.jump_buffer_timer = (self.jump_buffer_timer - delta).max(0.0);
+        self.coyote_timer = (self.coyote_timer - delta).max(0.0);
+        self.wall_coyote_timer = (self.wall_coyote_timer - delta).max(0.0);
+        self.wall_jump_lockout_timer = (self.wall_jump_lockout_timer - delta).max(0.0);
+        self.dash_buffer_timer = (self.dash_buffer_timer - delta).max(0.0);
+        self.ledge_regrab_timer = (self.ledge_regrab_timer - delta).max(0.0);
+        if self.is_dashing() {
+            self.dash_timer = (self.dash_timer - delta).max(0.0);
+            if !self.is_dashing() {
+                self.dash_cooldown_timer = self.config.dash_cooldown;
+            }
+        } else {
+            self.dash_cooldown_timer = (self.dash_cooldown_timer - delta).max(0.0);
+        }
+
+        if self.jump_buffer_timer <= 0.0 {
+            self.buffered_jump_cut_requested = false;
+        }
+    }
+
+    fn apply_gravity(
+        &self,
+        velocity: &mut Vector2,
+        delta: f32,
+        is_on_floor: bool,
+        input: MovementInput,
+    ) {
+        let fast_falling = input.vertical_direction >= FAST_FALL_INPUT_THRESHOLD;
+        let multiplier = if is_on_floor {
+            1.0
+        } else if fast_falling && velocity.y > 0.0 {
+            self.config.fast_fall_gravity_multiplier
+        } else if input.jump_held && velocity.y.abs() < self.config.apex_velocity_threshold {
+            self.config.apex_gravity_multiplier
+        } else if velocity.y > 0.0 {
+            self.config.fall_gravity_multiplier
+        } else {
+            1.0
+        };
+        velocity.y += self.config.gravity * multiplier * delta;
+
+        let max_fall_speed = if fast_falling {
+            self.config.fast_fall_max_speed
+        } else {
+            self.config.max_fall_speed
+        };
+        velocity.y = velocity.y.min(max_fall_speed);
+    }
+
+    fn apply_walk(
+        &mut self,
+        velocity: &mut Vector2,
+        delta: f32,
+        direction: f32,
+        is_on_floor: bool,
+    ) {
+        if self.wall_jump_lockout_timer > 0.0 {
+            return;
+        }
+
+        let accel = self.horizontal_acceleration(velocity.x, direction, is_on_floor);
+        let walk_speed = if self.crouching {
+            self.config.walk_speed * self.config.crouch_speed_multiplier
+        } else {
+            self.config.walk_speed
+        };
+        velocity.x = move_toward_scalar(velocity.x, direction * walk_speed, accel * delta);
+    }
+
+    /// Holding down crouches on solid floor; letting go stands up once the
+    /// owner reports room overhead.
+    fn update_crouch(&mut self, is_on_floor: bool, input: MovementInput) {
+        let wants_crouch =
+            is_on_floor && input.can_crouch && input.vertical_direction >= CROUCH_INPUT_THRESHOLD;
+        self.crouching = wants_crouch || (self.crouching && input.ceiling_blocks_standing);
+    }
+
+    fn floor_state(&self) -> MovementState {
+        if self.crouching {
+            MovementState::Crouch
+        } else {
+            MovementState::Floor
+        }
+    }
+
+    fn horizontal_acceleration(&self, velocity_x: f32, direction: f32, is_on_floor: bool) -> f32 {
+        let changing_direction = direction.abs() >= INPUT_DEADZONE
+            && velocity_x.abs() >= INPUT_DEADZONE
+            && direction.signum() != velocity_x.signum();
+
+        let base_accel = if is_on_floor {
+            if direction.abs() < INPUT_DEADZONE {
+                self.config.ground_decel_speed
+            } else {
+                self.config.ground_accel_speed
+            }
+        } else if direction.abs() < INPUT_DEADZONE {
+            self.config.air_decel_speed
+        } else {
+            self.config.air_accel_speed
+        };
+
+        if is_on_floor && changing_direction {
+            base_accel * self.config.turn_accel_multiplier
+        } else {
+            base_accel
+        }
+    }
+
+    fn can_jump(&self, is_on_floor: bool) -> bool {
+        self.jump_buffer_timer > 0.0 && (is_on_floor || self.coyote_timer > 0.0)
+    }
+
+    /// Jumps from a slope turn toward its normal on top of the walk speed,
+    /// and floor jumps carry the platform's velocity; coyote jumps have left
+    /// the floor, with the platform velocity already added, and go straight
+    /// up.
+    fn apply_jump(
+        &mut self,
+        velocity: &mut Vector2,
+        is_on_floor: bool,
+        floor_normal: Vector2,
+    ) -> bool {
+        let can_jump = self.can_jump(is_on_floor);
+        if can_jump {
+            let floor_normal = if is_on_floor {
+                floor_normal
+            } else {
+                Vector2::ZERO
+            };
+            let impulse = self.jump_impulse(floor_normal);
+            velocity.x += impulse.x;
+            velocity.y = impulse.y;
+            if is_on_floor {
+                *velocity += self.inherited_platform_velocity();
+            }
+            self.jump_buffer_timer = 0.0;
+            self.coyote_timer = 0.0;
+        }
+        can_jump
+    }
+
+    /// Capped, and a sinking platform never weakens a jump.
+    fn inherited_platform_velocity(&self) -> Vector2 {
+        let velocity = self
+            .platform_velocity
+            .limit_length(Some(self.config.max_inherited_platform_speed));
+        Vector2::new(velocity.x, velocity.y.min(0.0))
+    }
+
+    fn jump_impulse(&self, floor_normal: Vector2) -> Vector2 {
+        let upward = Vector2::new(0.0, self.config.jump_velocity);
+        if floor_normal.is_zero_approx()
+            || !is_floor_normal(floor_normal, self.config.floor_max_angle())
+        {
+            return upward;
+        }
+
+        let blend = self.config.slope_jump_normal_blend.clamp(0.0, 1.0);
+        let tilt = floor_normal.x.atan2(-floor_normal.y) * blend;
+        // Tilted, but scaled so the vertical part keeps the full jump speed.
+        upward + Vector2::new(tilt.tan() * -self.config.jump_velocity, 0.0)
+    }
+
+    /// Starts wall coyote time when the body leaves a wall without jumping
+    /// off it, like `coyote_timer` does for ledges.
+    fn track_wall_contact(&mut self, wall_direction: f32, is_on_floor: bool) {
+        let touching = !is_on_floor && wall_direction.abs() >= INPUT_DEADZONE;
+        if touching {
+            self.wall_coyote_timer = 0.0;
+            self.wall_side = wall_direction.signum();
+        } else if self.on_wall && !is_on_floor {
+            self.wall_coyote_timer = self.config.wall_coyote_time;
+        }
+        self.on_wall = touching;
+    }
+
+    fn can_wall_jump(&self, is_on_floor: bool) -> bool {
+        self.abilities.wall_jump
+            && self.jump_buffer_timer > 0.0
+            && !is_on_floor
+            && (self.on_wall || self.wall_coyote_timer > 0.0)
+    }
+
+    fn apply_wall_jump(&mut self, velocity: &mut Vector2, is_on_floor: bool) -> bool {
+        let can_wall_jump = self.can_wall_jump(is_on_floor);
+        if can_wall_jump {
+            velocity.x = -self.wall_side * self.config.wall_jump_kick_speed;
+            velocity.y = self.config.jump_velocity;
+            self.jump_buffer_timer = 0.0;
+            self.wall_coyote_timer = 0.0;
+            self.wall_jump_lockout_timer = self.config.wall_jump_lockout_time;
+            self.on_wall = false;
+        }
+        can_wall_jump
+    }
+
+    /// Runs after the floor and wall jumps, so coyote time never spends an
+    /// air jump.
+    fn apply_air_jump(&mut self, velocity: &mut Vector2, is_on_floor: bool) -> bool {
+        let can_air_jump = self.abilities.double_jump
+            && self.jump_buffer_timer > 0.0
+            && !is_on_floor
+            && self.air_jumps > 0;
+        if can_air_jump {
+            velocity.y = self.config.jump_velocity;
+            self.jump_buffer_timer = 0.0;
+            self.air_jumps -= 1;
+        }
+        can_air_jump
+    }
+
+    fn apply_wall_slide(&mut self, velocity: &mut Vector2, direction: f32) {
+        let pressing_into_wall = direction * self.wall_side >= INPUT_DEADZONE;
+        if self.on_wall && pressing_into_wall && velocity.y > 0.0 {
+            velocity.y = velocity.y.min(self.config.wall_slide_max_speed);
+            self.wall_sliding = true;
+        }
+    }
+
+    /// Grabs only while falling and holding toward the ledge, and not while
+    /// holding down, so a player can still fall past on purpose.
+    fn try_grab_ledge(
+        &mut self,
+        velocity: Vector2,
+        is_on_floor: bool,
+        input: MovementInput,
+    ) -> bool {
+        let side = input.ledge_direction;
+        let holding_toward = input.direction * side >= INPUT_DEADZONE;
+        if is_on_floor
+            || velocity.y < 0.0
+            || self.is_dashing()
+            || self.ledge_regrab_timer > 0.0
+            || side.abs() < INPUT_DEADZONE
+            || !holding_toward
+            || input.vertical_direction >= LEDGE_INPUT_THRESHOLD
+        {
+            return false;
+        }
+
+        self.ledge_side = Some(side.signum());
+        self.air_jumps = self.config.air_jumps;
+        self.air_dash_charges = self.config.air_dash_charges;
+        self.jump_buffer_timer = 0.0;
+        self.coyote_timer = 0.0;
+        self.state = MovementState::LedgeHang;
+        true
+    }
+
+    /// Jump or up mantles, down drops; losing the ledge, e.g. to a crumbling
+    /// platform, drops too.
+    fn hang_from_ledge(&mut self, input: MovementInput) -> Vector2 {
+        let ledge_lost = input.ledge_direction.abs() < INPUT_DEADZONE;
+        let mantling = !ledge_lost
+            && (input.jump_just_pressed || input.vertical_direction <= -LEDGE_INPUT_THRESHOLD);
+        let dropping = ledge_lost || input.vertical_direction >= LEDGE_INPUT_THRESHOLD;
+
+        if mantling || dropping {
+            self.ledge_side = None;
+            self.mantled_this_frame = mantling;
+            if dropping {
+                self.ledge_regrab_timer = self.config.ledge_regrab_time;
+            }
+            self.state = MovementState::Air;
+        } else {
+            self.state = MovementState::LedgeHang;
+        }
+        Vector2::ZERO
+    }
+
+    fn try_start_dash(&mut self, is_on_floor: bool) -> bool {
+        let has_charge = is_on_floor || self.air_dash_charges > 0;
+        if !self.abilities.dash
+            || self.dash_buffer_timer <= 0.0
+            || self.dash_cooldown_timer > 0.0
+            || !has_charge
+            || self.buffered_dash_direction.is_zero_approx()
+        {
+            return false;
+        }
+
+        if !is_on_floor {
+            self.air_dash_charges -= 1;
+        }
+        self.dash_timer = self.config.dash_duration;
+        self.dash_buffer_timer = 0.0;
+        self.dash_velocity = self.buffered_dash_direction.normalized() * self.config.dash_speed;
+        true
+    }
+
+    fn airborne_state(&self) -> MovementState {
+        if self.wall_sliding {
+            MovementState::WallSlide
+        } else {
+            MovementState::Air
+        }
+    }
+
+    fn apply_jump_cut(&self, velocity: &mut Vector2) {
+        if velocity.y < 0.0 {
+            velocity.y *= self.config.jump_release_velocity_factor;
+        }
+    }
+
+    pub fn is_walking(&self, velocity: Vector2) -> bool {
+        velocity.x.abs() > self.config.min_walk_speed
+    }
+
+    pub fn is_walking_or_pressing(&self, velocity: Vector2, input_direction: f32) -> bool {
+        input_direction.abs() >= INPUT_DEADZONE || self.is_walking(velocity)
+    }
+
+    pub fn climb_velocity(&self, input: MovementInput) -> Vector2 {
+        climb_velocity(input, self.config.climb_speed)
+    }
+}
+
+/// Whether a collision normal is ground the body can stand on.
+pub fn is_floor_normal(normal: Vector2, floor_max_angle: f32) -> bool {
+    -normal.y >= floor_max_angle.cos() - FLOOR_ANGLE_EPSILON
+}
+
+/// Whether a collision normal belongs to a wall: too steep for floor and
+/// not a ceiling either, so slopes never read as walls.
+pub fn is_wall_normal(normal: Vector2, floor_max_angle: f32) -> bool {
+    normal.y.abs() < floor_max_angle.cos() - FLOOR_ANGLE_EPSILON
+}
+
+pub fn climb_velocity(input: MovementInput, climb_speed: f32) -> Vector2 {
+    Vector2::new(
+        axis_or_zero(input.direction) * climb_speed,
+        axis_or_zero(input.vertical_direction) * climb_speed,
+    )
+}
+
+fn axis_or_zero(value: f32) -> f32 {
+    if value.abs() >= INPUT_DEADZONE {
+        value
+    } else {
+        0.0
+    }
+}
+
+fn move_toward_scalar(current: f32, target: f32, max_delta: f32) -> f32 {
+    if (target - current).abs() <= max_delta {
+        target
+    } else {
+        current + (target - current).signum() * max_delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> MovementConfig {
+        MovementConfig {
+            gravity: 0.0,
+            walk_speed: 120.0,
+            ground_accel_speed: 720.0,
+            ground_decel_speed: 1080.0,
+            air_accel_speed: 540.0,
+            air_decel_speed: 720.0,
+            turn_accel_multiplier: 1.4,
+            jump_velocity: -300.0,
+            jump_buffer_time: 0.12,
+            coyote_time: 0.10,
+            air_jumps: 0,
+            jump_release_velocity_factor: 0.5,
+            apex_gravity_multiplier: 0.5,
+            apex_velocity_threshold: 40.0,
+            fall_gravity_multiplier: 1.5,
+            max_fall_speed: 400.0,
+            fast_fall_gravity_multiplier: 2.5,
+            fast_fall_max_speed: 560.0,
+            min_walk_speed: 0.1,
+            floor_max_angle_degrees: 46.0,
+            floor_snap_length: 8.0,
+            slope_jump_normal_blend: 0.3,
+            max_inherited_platform_speed: 240.0,
+            climb_speed: 80.0,
+            wall_slide_max_speed: 60.0,
+            wall_jump_kick_speed: 150.0,
+            wall_jump_lockout_time: 0.15,
+            wall_coyote_time: 0.10,
+            ledge_regrab_time: 0.25,
+            crouch_speed_multiplier: 0.5,
+            dash_speed: 300.0,
+            dash_duration: 0.15,
+            dash_cooldown: 0.25,
+            dash_buffer_time: 0.12,
+            air_dash_charges: 1,
+        }
+    }
+
+    fn dash_input(direction: Vector2) -> MovementInput {
+        MovementInput {
+            dash_just_pressed: true,
+            dash_direction: direction,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn buffers_jump_until_landing() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let airborne_velocity = movement.physics_process(
+            Vector2::new(0.0, 80.0),
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(airborne_velocity.y, 80.0);
+
+        let jumped_velocity =
+            movement.physics_process(airborne_velocity, true, delta, MovementInput::default());
+
+        assert_eq!(jumped_velocity.y, movement.config.jump_velocity);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    #[test]
+    fn allows_coyote_jump_after_walking_off_ledge() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        movement.post_physics_update(true);
+        movement.physics_process(Vector2::ZERO, true, delta, MovementInput::default());
+        movement.post_physics_update(false);
+
+        let jumped_velocity = movement.physics_process(
+            Vector2::ZERO,
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(jumped_velocity.y, movement.config.jump_velocity);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    #[test]
+    fn jump_release_cuts_upward_speed() {
+        let mut movement = PlayerMovement::new(test_config());
+
+        let velocity = movement.physics_process(
+            Vector2::new(0.0, -200.0),
+            false,
+            0.016,
+            MovementInput {
+                jump_just_released: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(velocity.y, -100.0);
+    }
+
+    #[test]
+    fn leaving_floor_via_jump_does_not_restore_coyote_time() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        movement.post_physics_update(true);
+
+        let jumped_velocity = movement.physics_process(
+            Vector2::ZERO,
+            true,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(jumped_velocity.y, movement.config.jump_velocity);
+
+        let second_jump_attempt = movement.physics_process(
+            Vector2::new(0.0, -180.0),
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(second_jump_attempt.y, -180.0);
+    }
+
+    #[test]
+    fn reset_transient_state_clears_buffered_jump() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let airborne_velocity = movement.physics_process(
+            Vector2::new(0.0, 80.0),
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        movement.reset_transient_state();
+
+        let grounded_velocity =
+            movement.physics_process(airborne_velocity, true, delta, MovementInput::default());
+
+        assert_eq!(grounded_velocity.y, 80.0);
+        assert_eq!(movement.state, MovementState::Floor);
+    }
+
+    #[test]
+    fn buffered_jump_release_cuts_jump_on_landing() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let airborne_velocity = movement.physics_process(
+            Vector2::new(0.0, 80.0),
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+
+        let released_velocity = movement.physics_process(
+            airborne_velocity,
+            false,
+            delta,
+            MovementInput {
+                jump_just_released: true,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(released_velocity.y, 80.0);
+
+        let jumped_velocity =
+            movement.physics_process(released_velocity, true, delta, MovementInput::default());
+
+        assert_eq!(jumped_velocity.y, movement.config.jump_velocity * 0.5);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    #[test]
+    fn wall_slide_caps_fall_speed_while_pressing_into_wall() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let sliding_velocity = movement.physics_process(
+            Vector2::new(0.0, 200.0),
+            false,
+            delta,
+            MovementInput {
+                direction: 1.0,
+                wall_direction: 1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(sliding_velocity.y, movement.config.wall_slide_max_speed);
+        assert_eq!(movement.state, MovementState::WallSlide);
+
+        let released_velocity = movement.physics_process(
+            Vector2::new(0.0, 200.0),
+            false,
+            delta,
+            MovementInput {
+                wall_direction: 1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+
+        assert_eq!(released_velocity.y, 200.0);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    #[test]
+    fn wall_jump_kicks_away_and_locks_out_input() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let jumped_velocity = movement.physics_process(
+            Vector2::new(0.0, 40.0),
+            false,
+            delta,
+            MovementInput {
+                direction: 1.0,
+                jump_just_pressed: true,
+                wall_direction: 1.0,
+                ..Default::default()
+            },
+        );
+        assert!(movement.jumped_this_frame());
+        movement.post_physics_update(false);
+        assert_eq!(
+            jumped_velocity,
+            Vector2::new(
+                -movement.config.wall_jump_kick_speed,
+                movement.config.jump_velocity
+            )
+        );
+
+        let locked_velocity = movement.physics_process(
+            jumped_velocity,
+            false,
+            delta,
+            MovementInput {
+                direction: 1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(locked_velocity.x, -movement.config.wall_jump_kick_speed);
+
+        let steered_velocity = movement.physics_process(
+            locked_velocity,
+            false,
+            movement.config.wall_jump_lockout_time as f64,
+            MovementInput {
+                direction: 1.0,
+                ..Default::default()
+            },
+        );
+
+        assert!(steered_velocity.x > locked_velocity.x);
+    }
+
+    #[test]
+    fn allows_wall_coyote_jump_after_leaving_wall() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        movement.physics_process(
+            Vector2::ZERO,
+            false,
+            delta,
+            MovementInput {
+                wall_direction: -1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        movement.physics_process(Vector2::ZERO, false, delta, MovementInput::default());
+        movement.post_physics_update(false);
+
+        let jumped_velocity = movement.physics_process(
+            Vector2::ZERO,
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(jumped_velocity.x, movement.config.wall_jump_kick_speed);
+        assert_eq!(jumped_velocity.y, movement.config.jump_velocity);
+    }
+
+    #[test]
+    fn leaving_wall_via_wall_jump_does_not_restore_wall_coyote_time() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.016;
+
+        let jumped_velocity = movement.physics_process(
+            Vector2::ZERO,
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                wall_direction: 1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+
+        let second_jump_attempt = movement.physics_process(
+            jumped_velocity,
+            false,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(second_jump_attempt, jumped_velocity);
+    }
+
+    #[test]
+    fn dash_holds_fixed_velocity_without_gravity() {
+        let mut config = test_config();
+        config.gravity = 980.0;
+        let mut movement = PlayerMovement::new(config);
+        let delta = 0.06;
+
+        let dash_velocity = movement.physics_process(
+            Vector2::new(0.0, 120.0),
+            false,
+            delta,
+            dash_input(Vector2::new(1.0, -1.0)),
+        );
+        movement.post_physics_update(false);
+        let expected = Vector2::new(1.0, -1.0).normalized() * movement.config.dash_speed;
+        assert_eq!(dash_velocity, expected);
+        assert_eq!(movement.state, MovementState::Dash);
+
+        let held_velocity =
+            movement.physics_process(Vector2::ZERO, false, delta, MovementInput::default());
+        movement.post_physics_update(false);
+        assert_eq!(held_velocity, expected);
+
+        movement.physics_process(held_velocity, false, delta, MovementInput::default());
+        movement.post_physics_update(false);
+        let falling_velocity =
+            movement.physics_process(held_velocity, false, delta, MovementInput::default());
+
+        assert!(!movement.is_dashing());
+        assert!(falling_velocity.y > held_velocity.y);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    #[test]
+    fn air_dash_charge_refills_on_landing() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.2;
+
+        movement.physics_process(Vector2::ZERO, false, delta, dash_input(Vector2::RIGHT));
+        movement.post_physics_update(false);
+        movement.physics_process(Vector2::ZERO, false, delta, MovementInput::default());
+        movement.post_physics_update(false);
+        movement.physics_process(Vector2::ZERO, false, delta, MovementInput::default());
+        movement.post_physics_update(false);
+
+        let no_charge_velocity =
+            movement.physics_process(Vector2::ZERO, false, delta, dash_input(Vector2::RIGHT));
+        movement.post_physics_update(false);
+        assert_eq!(no_charge_velocity, Vector2::ZERO);
+
+        movement.physics_process(Vector2::ZERO, true, delta, MovementInput::default());
+        movement.post_physics_update(false);
+        let refilled_velocity =
+            movement.physics_process(Vector2::ZERO, false, delta, dash_input(Vector2::LEFT));
+
+        assert_eq!(refilled_velocity.x, -movement.config.dash_speed);
+    }
+
+    #[test]
+    fn buffers_dash_until_cooldown_ends() {
+        let mut movement = PlayerMovement::new(test_config());
+        let delta = 0.1;
+
+        movement.physics_process(Vector2::ZERO, true, delta, dash_input(Vector2::RIGHT));
+        movement.post_physics_update(true);
+        movement.physics_process(Vector2::ZERO, true, delta, MovementInput::default());
+        movement.post_physics_update(true);
+        movement.physics_process(Vector2::ZERO, true, delta, MovementInput::default());
+        movement.post_physics_update(true);
+        assert!(!movement.is_dashing());
+
+        let cooling_velocity =
+            movement.physics_process(Vector2::ZERO, true, 0.15, dash_input(Vector2::LEFT));
+        movement.post_physics_update(true);
+        assert!(!movement.is_dashing());
+        assert_eq!(cooling_velocity, Vector2::ZERO);
+
+        let buffered_velocity =
+            movement.physics_process(Vector2::ZERO, true, 0.11, MovementInput::default());
+
+        assert_eq!(buffered_velocity.x, -movement.config.dash_speed);
+        assert_eq!(movement.state, MovementState::Dash);
+    }
+
+    #[test]
+    fn double_jump_spends_air_jumps_until_landing() {
+        let mut movement = PlayerMovement::new(MovementConfig {
+            air_jumps: 1,
+            ..test_config()
+        });
+        let jump = MovementInput {
+            jump_just_pressed: true,
+            ..Default::default()
+        };
+
+        let air_jump_velocity =
+            movement.physics_process(Vector2::new(0.0, 80.0), false, 0.016, jump);
+        assert!(movement.jumped_this_frame());
+        movement.post_physics_update(false);
+        assert_eq!(air_jump_velocity.y, movement.config.jump_velocity);
+
+        let spent_velocity = movement.physics_process(Vector2::new(0.0, 80.0), false, 0.2, jump);
+        movement.post_physics_update(false);
+        assert_eq!(spent_velocity.y, 80.0);
+
+        movement.physics_process(Vector2::ZERO, true, 0.2, MovementInput::default());
+        movement.post_physics_update(true);
+        movement.physics_process(Vector2::ZERO, false, 0.2, MovementInput::default());
+        movement.post_physics_update(false);
+        let refilled_velocity =
+            movement.physics_process(Vector2::new(0.0, 80.0), false, 0.016, jump);
+        assert_eq!(refilled_velocity.y, movement.config.jump_velocity);
+    }
+
+    #[test]
+    fn locked_abilities_disable_their_moves() {
+        let mut movement = PlayerMovement::new(MovementConfig {
+            air_jumps: 1,
+            ..test_config()
+        });
+        movement.abilities = MovementAbilities {
+            double_jump: false,
+            dash: false,
+            wall_jump: false,
+        };
+
+        let air_velocity = movement.physics_process(
+            Vector2::new(0.0, 80.0),
+            false,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                wall_direction: 1.0,
+                ..Default::default()
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(air_velocity, Vector2::new(0.0, 80.0));
+
+        movement.physics_process(Vector2::ZERO, true, 0.016, dash_input(Vector2::RIGHT));
+        assert!(!movement.is_dashing());
+
+        movement.abilities.dash = true;
+        movement.physics_process(Vector2::ZERO, true, 0.016, MovementInput::default());
+        assert!(movement.is_dashing());
+    }
+
+    fn gravity_config() -> MovementConfig {
+        MovementConfig {
+            gravity: 1000.0,
+            ..test_config()
+        }
+    }
+
+    /// Frames from a floor jump until the body is back at its take-off height.
+    fn airborne_frames(input: MovementInput) -> usize {
+        let mut movement = PlayerMovement::new(MovementConfig::default());
+        let delta = 1.0 / 60.0;
+        let mut velocity = movement.physics_process(
+            Vector2::ZERO,
+            true,
+            delta,
+            MovementInput {
+                jump_just_pressed: true,
+                ..input
+            },
+        );
+        movement.post_physics_update(false);
+        let mut height = -velocity.y * delta as f32;
+
+        let mut frames = 1;
+        while height > 0.0 {
+            velocity = movement.physics_process(velocity, false, delta, input);
+            movement.post_physics_update(false);
+            height -= velocity.y * delta as f32;
+            frames += 1;
+        }
+        frames
+    }
+
+    #[test]
+    fn apex_gravity_is_reduced_only_while_jump_is_held() {
+        let mut movement = PlayerMovement::new(gravity_config());
+        let held = MovementInput {
+            jump_held: true,
+            ..Default::default()
+        };
+
+        let near_apex = movement.physics_process(Vector2::new(0.0, -10.0), false, 0.01, held);
+        let rising = movement.physics_process(Vector2::new(0.0, -100.0), false, 0.01, held);
+        let released =
+            movement.physics_process(Vector2::new(0.0, -10.0), false, 0.01, Default::default());
+
+        assert_eq!(near_apex.y, -5.0);
+        assert_eq!(rising.y, -90.0);
+        assert_eq!(released.y, 0.0);
+    }
+
+    #[test]
+    fn falls_faster_than_it_rises_up_to_terminal_velocity() {
+        let mut movement = PlayerMovement::new(gravity_config());
+
+        let falling =
+            movement.physics_process(Vector2::new(0.0, 100.0), false, 0.01, Default::default());
+        let terminal =
+            movement.physics_process(Vector2::new(0.0, 395.0), false, 0.01, Default::default());
+
+        assert_eq!(falling.y, 115.0);
+        assert_eq!(terminal.y, movement.config.max_fall_speed);
+    }
+
+    #[test]
+    fn holding_down_fast_falls_only_on_the_way_down() {
+        let mut movement = PlayerMovement::new(gravity_config());
+        let down = MovementInput {
+            vertical_direction: 1.0,
+            ..Default::default()
+        };
+
+        let falling = movement.physics_process(Vector2::new(0.0, 100.0), false, 0.01, down);
+        let terminal = movement.physics_process(Vector2::new(0.0, 550.0), false, 0.01, down);
+        let rising = movement.physics_process(Vector2::new(0.0, -100.0), false, 0.01, down);
+
+        assert_eq!(falling.y, 125.0);
+        assert_eq!(terminal.y, movement.config.fast_fall_max_speed);
+        assert_eq!(rising.y, -90.0);
+    }
+
+    #[test]
+    fn held_jumps_hang_longer_and_fast_falls_land_sooner() {
+        let tapped = airborne_frames(MovementInput::default());
+        let held = airborne_frames(MovementInput {
+            jump_held: true,
+            ..Default::default()
+        });
+        let fast_fall = airborne_frames(MovementInput {
+            vertical_direction: 1.0,
+            ..Default::default()
+        });
+
+        assert!(held > tapped, "held {held} vs tapped {tapped}");
+        assert!(
+            fast_fall < tapped,
+            "fast fall {fast_fall} vs tapped {tapped}"
+        );
+    }
+
+    fn floor_jump(config: MovementConfig, velocity: Vector2, floor_normal: Vector2) -> Vector2 {
+        let mut movement = PlayerMovement::new(config);
+        movement.physics_process(
+            velocity,
+            true,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                direction: velocity.x.signum(),
+                floor_normal,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn slopes_up_to_the_max_floor_angle_are_floor_not_wall() {
+        let max_angle = test_config().floor_max_angle();
+        let gentle = Vector2::UP.rotated(22.5_f32.to_radians());
+        let steep = Vector2::UP.rotated(-45.0_f32.to_radians());
+        let too_steep = Vector2::UP.rotated(60.0_f32.to_radians());
+
+        assert!(is_floor_normal(gentle, max_angle));
+        assert!(is_floor_normal(steep, max_angle));
+        assert!(!is_wall_normal(steep, max_angle));
+        assert!(is_wall_normal(too_steep, max_angle));
+        assert!(is_wall_normal(Vector2::LEFT, max_angle));
+        assert!(!is_wall_normal(Vector2::DOWN, max_angle));
+        assert!(!is_floor_normal(Vector2::DOWN, max_angle));
+    }
+
+    #[test]
+    fn jumps_from_slopes_turn_toward_the_normal_at_full_height() {
+        let walk = Vector2::new(-120.0, 0.0);
+        let normal = Vector2::UP.rotated(-45.0_f32.to_radians());
+
+        let velocity = floor_jump(test_config(), walk, normal);
+
+        assert_eq!(velocity.y, -300.0);
+        let push = velocity.x - walk.x;
+        let tilt = (45.0_f32 * 0.3).to_radians();
+        assert!((push + tilt.tan() * 300.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn full_blend_jumps_leave_along_the_slope_normal() {
+        let walk = Vector2::new(-120.0, 0.0);
+        let normal = Vector2::UP.rotated(-22.5_f32.to_radians());
+        let along_normal = MovementConfig {
+            slope_jump_normal_blend: 1.0,
+            ..test_config()
+        };
+
+        let velocity = floor_jump(along_normal, walk, normal);
+
+        let impulse = velocity - walk;
+        assert_eq!(impulse.y, -300.0);
+        assert!(impulse.normalized().dot(normal) > 0.999);
+    }
+
+    #[test]
+    fn uphill_slope_jumps_keep_moving_uphill() {
+        let walk = Vector2::new(120.0, 0.0);
+        // Rising to the right, so the normal leans left.
+        let normal = Vector2::UP.rotated(-45.0_f32.to_radians());
+
+        let velocity = floor_jump(MovementConfig::default(), walk, normal);
+
+        assert_eq!(velocity.y, MovementConfig::default().jump_velocity);
+        assert!(velocity.x > 0.0 && velocity.x < walk.x, "{velocity:?}");
+    }
+
+    #[test]
+    fn slope_jumps_go_straight_up_without_blend_or_on_flat_ground() {
+        let walk = Vector2::new(120.0, 0.0);
+        let normal = Vector2::UP.rotated(22.5_f32.to_radians());
+        let upright = MovementConfig {
+            slope_jump_normal_blend: 0.0,
+            ..test_config()
+        };
+
+        assert_eq!(
+            floor_jump(upright, walk, normal),
+            Vector2::new(120.0, -300.0)
+        );
+        assert_eq!(
+            floor_jump(test_config(), walk, Vector2::ZERO),
+            Vector2::new(120.0, -300.0)
+        );
+        assert_eq!(
+            floor_jump(test_config(), walk, Vector2::UP),
+            Vector2::new(120.0, -300.0)
+        );
+    }
+
+    fn on_platform(platform_velocity: Vector2) -> MovementInput {
+        MovementInput {
+            platform_velocity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn jumps_from_moving_platforms_carry_capped_platform_velocity() {
+        let mut movement = PlayerMovement::new(test_config());
+        let cap = movement.config.max_inherited_platform_speed;
+
+        let velocity = movement.physics_process(
+            Vector2::ZERO,
+            true,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                ..on_platform(Vector2::new(100.0, -50.0))
+            },
+        );
+        assert_eq!(velocity, Vector2::new(100.0, -350.0));
+
+        let mut movement = PlayerMovement::new(test_config());
+        let velocity = movement.physics_process(
+            Vector2::ZERO,
+            true,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                ..on_platform(Vector2::new(cap * 3.0, 0.0))
+            },
+        );
+        assert_eq!(velocity.x, cap);
+    }
+
+    #[test]
+    fn sinking_platforms_do_not_weaken_jumps() {
+        let mut movement = PlayerMovement::new(test_config());
+
+        let velocity = movement.physics_process(
+            Vector2::ZERO,
+            true,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                ..on_platform(Vector2::new(0.0, 80.0))
+            },
+        );
+
+        assert_eq!(velocity.y, movement.config.jump_velocity);
+    }
+
+    #[test]
+    fn walking_off_a_platform_keeps_its_velocity_once() {
+        let mut movement = PlayerMovement::new(test_config());
+        let platform = Vector2::new(90.0, 0.0);
+
+        movement.physics_process(Vector2::ZERO, true, 0.016, on_platform(platform));
+        movement.post_physics_update(true);
+        movement.physics_process(Vector2::ZERO, true, 0.016, on_platform(platform));
+        movement.post_physics_update(false);
+
+        let launched =
+            movement.physics_process(Vector2::ZERO, false, 0.016, MovementInput::default());
+        let air_drag = movement.config.air_decel_speed * 0.016;
+        assert!((launched.x - (platform.x - air_drag)).abs() < 0.001);
+        movement.post_physics_update(false);
+
+        let next = movement.physics_process(launched, false, 0.016, MovementInput::default());
+        assert!(next.x < launched.x);
+
+        let coyote_jump = movement.physics_process(
+            next,
+            false,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                ..Default::default()
+            },
+        );
+        assert!(coyote_jump.x <= next.x);
+        assert_eq!(coyote_jump.y, movement.config.jump_velocity);
+    }
+
+    fn toward_ledge(direction: f32) -> MovementInput {
+        MovementInput {
+            direction,
+            ledge_direction: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn hanging_movement() -> PlayerMovement {
+        let mut movement = PlayerMovement::new(test_config());
+        let velocity =
+            movement.physics_process(Vector2::new(0.0, 90.0), false, 0.016, toward_ledge(1.0));
+        movement.post_physics_update(false);
+        assert_eq!(velocity, Vector2::ZERO);
+        assert_eq!(movement.state, MovementState::LedgeHang);
+        movement
+    }
+
+    #[test]
+    fn grabs_ledges_only_while_falling_toward_them() {
+        let mut rising = PlayerMovement::new(test_config());
+        rising.physics_process(Vector2::new(0.0, -90.0), false, 0.016, toward_ledge(1.0));
+        assert_eq!(rising.ledge_side(), None);
+
+        let mut away = PlayerMovement::new(test_config());
+        away.physics_process(Vector2::new(0.0, 90.0), false, 0.016, toward_ledge(-1.0));
+        assert_eq!(away.ledge_side(), None);
+
+        let mut dropping = PlayerMovement::new(test_config());
+        dropping.physics_process(
+            Vector2::new(0.0, 90.0),
+            false,
+            0.016,
+            MovementInput {
+                vertical_direction: 1.0,
+                ..toward_ledge(1.0)
+            },
+        );
+        assert_eq!(dropping.ledge_side(), None);
+
+        assert_eq!(hanging_movement().ledge_side(), Some(1.0));
+    }
+
+    #[test]
+    fn hanging_holds_still_until_jump_or_up_mantles() {
+        let mut movement = hanging_movement();
+
+        let idle = movement.physics_process(Vector2::ZERO, false, 0.016, toward_ledge(0.0));
+        movement.post_physics_update(false);
+        assert_eq!(idle, Vector2::ZERO);
+        assert_eq!(movement.state, MovementState::LedgeHang);
+        assert!(!movement.mantled_this_frame());
+
+        movement.physics_process(
+            Vector2::ZERO,
+            false,
+            0.016,
+            MovementInput {
+                jump_just_pressed: true,
+                ..toward_ledge(0.0)
+            },
+        );
+        assert!(movement.mantled_this_frame());
+        assert!(!movement.jumped_this_frame());
+        assert_eq!(movement.ledge_side(), None);
+
+        let mut movement = hanging_movement();
+        movement.physics_process(
+            Vector2::ZERO,
+            false,
+            0.016,
+            MovementInput {
+                vertical_direction: -1.0,
+                ..toward_ledge(0.0)
+            },
+        );
+        assert!(movement.mantled_this_frame());
+    }
+
+    #[test]
+    fn dropping_from_a_ledge_blocks_regrabbing_briefly() {
+        let mut movement = hanging_movement();
+
+        movement.physics_process(
+            Vector2::ZERO,
+            false,
+            0.016,
+            MovementInput {
+                vertical_direction: 1.0,
+                ..toward_ledge(1.0)
+            },
+        );
+        movement.post_physics_update(false);
+        assert_eq!(movement.ledge_side(), None);
+        assert!(!movement.mantled_this_frame());
+
+        let falling = movement.physics_process(Vector2::ZERO, false, 0.016, toward_ledge(1.0));
+        assert_eq!(movement.ledge_side(), None);
+        assert_eq!(movement.state, MovementState::Air);
+        assert!(falling.x > 0.0);
+
+        let regrab_frames = (movement.config.ledge_regrab_time / 0.016).ceil() as usize;
+        for _ in 0..regrab_frames {
+            movement.physics_process(Vector2::new(0.0, 90.0), false, 0.016, toward_ledge(1.0));
+        }
+        assert_eq!(movement.ledge_side(), Some(1.0));
+    }
+
+    #[test]
+    fn losing_the_ledge_drops_the_player() {
+        let mut movement = hanging_movement();
+        let mut no_ledge = toward_ledge(1.0);
+        no_ledge.ledge_direction = 0.0;
+
+        movement.physics_process(Vector2::ZERO, false, 0.016, no_ledge);
+        movement.post_physics_update(false);
+
+        assert_eq!(movement.ledge_side(), None);
+        assert_eq!(movement.state, MovementState::Air);
+    }
+
+    fn holding_down(can_crouch: bool) -> MovementInput {
+        MovementInput {
+            direction: 1.0,
+            vertical_direction: 1.0,
+            can_crouch,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn crouching_walks_slower_on_solid_floor_only() {
+        let mut movement = PlayerMovement::new(test_config());
+        let crawl = movement.physics_process(Vector2::ZERO, true, 1.0, holding_down(true));
+        movement.post_physics_update(true);
+        assert!(movement.is_crouching());
+        assert_eq!(movement.state, MovementState::Crouch);
+        assert_eq!(
+            crawl.x,
+            movement.config.walk_speed * movement.config.crouch_speed_multiplier
+        );
+
+        let mut on_platform = PlayerMovement::new(test_config());
+        let walk = on_platform.physics_process(Vector2::ZERO, true, 1.0, holding_down(false));
+        on_platform.post_physics_update(true);
+        assert!(!on_platform.is_crouching());
+        assert_eq!(on_platform.state, MovementState::Floor);
+        assert_eq!(walk.x, on_platform.config.walk_speed);
+
+        let mut airborne = PlayerMovement::new(test_config());
+        airborne.physics_process(Vector2::ZERO, false, 0.016, holding_down(true));
+        assert!(!airborne.is_crouching());
+    }
+
+    #[test]
+    fn a_ceiling_keeps_the_player_crouched() {
+        let mut movement = PlayerMovement::new(test_config());
+        movement.physics_process(Vector2::ZERO, true, 0.016, holding_down(true));
+        movement.post_physics_update(true);
+
+        let released = MovementInput {
+            ceiling_blocks_standing: true,
+            ..Default::default()
+        };
+        movement.physics_process(Vector2::ZERO, true, 0.016, released);
+        movement.post_physics_update(true);
+        assert!(movement.is_crouching());
+        assert_eq!(movement.state, MovementState::Crouch);
+
+        movement.physics_process(Vector2::ZERO, true, 0.016, MovementInput::default());
+        movement.post_physics_update(true);
+        assert!(!movement.is_crouching());
+        assert_eq!(movement.state, MovementState::Floor);
+    }
+
+    #[test]
+    fn turn_acceleration_is_snappier_on_ground() {
+        let mut movement = PlayerMovement::new(test_config());
+        let mut baseline_config = test_config();
+        baseline_config.turn_accel_multiplier = 1.0;
+        let mut baseline = PlayerMovement::new(baseline_config);
+
+        let tuned_velocity = movement.physics_process(
+            Vector2::new(120.0, 0.0),
+            true,
+            0.1,
+            MovementInput {
+                direction: -1.0,
+                ..Default::default()
+            },
+        );
+
+        let baseline_velocity = baseline.physics_process(
+            Vector2::new(120.0, 0.0),
+            true,
+            0.1,
+            MovementInput {
+                direction: -1.0,
+                ..Default::default()
+            },
+        );
+
+        assert!(tuned_velocity.x < baseline_velocity.x);
+    }
+
+    #[test]
+    fn walking_or_pressing_treats_wall_push_as_walk_intent() {
+        let movement = PlayerMovement::new(test_config());
+
+        assert!(movement.is_walking_or_pressing(Vector2::ZERO, 1.0));
+        assert!(movement.is_walking_or_pressing(Vector2::new(5.0, 0.0), 0.0));
+        assert!(!movement.is_walking_or_pressing(Vector2::ZERO, 0.0));
+    }
+
+    #[test]
+    fn climb_velocity_maps_cardinal_input_directly() {
+        assert_eq!(
+            climb_velocity(
+                MovementInput {
+                    direction: 1.0,
+                    vertical_direction: -1.0,
+                    ..Default::default()
+                },
+                80.0,
+            ),
+            Vector2::new(80.0, -80.0)
+        );
+    }
+
+    #[test]
+    fn climb_velocity_ignores_tiny_input() {
+        assert_eq!(
+            climb_velocity(
+                MovementInput {
+                    direction: 0.005,
+                    vertical_direction: -0.005,
+                    ..Default::default()
+                },
+                80.0,
+            ),
+            Vector2::ZERO
+        );
+    }
+}
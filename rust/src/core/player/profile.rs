@@ -0,0 +1,248 @@
+//! Checks for movement profiles, the `MovementProfile` resources designers
+//! use to tune `MovementConfig` without rebuilding.
+
+use std::fmt;
+
+use super::MovementConfig;
+
+/// A field whose value the movement code cannot use.
+#[derive(Debug, PartialEq)]
+pub struct ProfileError {
+    pub key: &'static str,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.key, self.reason)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Rule {
+    Positive,
+    NonNegative,
+    Negative,
+    /// `0.0..=1.0`.
+    Fraction,
+    /// Degrees in `0.0..90.0`.
+    FloorAngle,
+}
+
+impl Rule {
+    fn accepts(self, value: f32) -> bool {
+        value.is_finite()
+            && match self {
+                Self::Positive => value > 0.0,
+                Self::NonNegative => value >= 0.0,
+                Self::Negative => value < 0.0,
+                Self::Fraction => (0.0..=1.0).contains(&value),
+                Self::FloorAngle => (0.0..90.0).contains(&value),
+            }
+    }
+
+    fn reason(self) -> &'static str {
+        match self {
+            Self::Positive => "must be a positive number",
+            Self::NonNegative => "must not be negative",
+            Self::Negative => "must be negative (up is -y)",
+            Self::Fraction => "must be between 0 and 1",
+            Self::FloorAngle => "must be at least 0 and below 90 degrees",
+        }
+    }
+}
+
+/// Rejects values the movement code cannot work with; the player runs it on
+/// every profile it loads.
+pub fn validate(config: &MovementConfig) -> Result<(), ProfileError> {
+    let rules = [
+        ("gravity", config.gravity, Rule::Positive),
+        ("walk_speed", config.walk_speed, Rule::Positive),
+        (
+            "ground_accel_speed",
+            config.ground_accel_speed,
+            Rule::Positive,
+        ),
+        (
+            "ground_decel_speed",
+            config.ground_decel_speed,
+            Rule::Positive,
+        ),
+        ("air_accel_speed", config.air_accel_speed, Rule::Positive),
+        ("air_decel_speed", config.air_decel_speed, Rule::NonNegative),
+        (
+            "turn_accel_multiplier",
+            config.turn_accel_multiplier,
+            Rule::Positive,
+        ),
+        ("jump_velocity", config.jump_velocity, Rule::Negative),
+        ("jump_buffer_time", config.jump_buffer_time, Rule::Positive),
+        ("coyote_time", config.coyote_time, Rule::NonNegative),
+        (
+            "jump_release_velocity_factor",
+            config.jump_release_velocity_factor,
+            Rule::Fraction,
+        ),
+        (
+            "apex_gravity_multiplier",
+            config.apex_gravity_multiplier,
+            Rule::Positive,
+        ),
+        (
+            "apex_velocity_threshold",
+            config.apex_velocity_threshold,
+            Rule::NonNegative,
+        ),
+        (
+            "fall_gravity_multiplier",
+            config.fall_gravity_multiplier,
+            Rule::Positive,
+        ),
+        ("max_fall_speed", config.max_fall_speed, Rule::Positive),
+        (
+            "fast_fall_gravity_multiplier",
+            config.fast_fall_gravity_multiplier,
+            Rule::Positive,
+        ),
+        (
+            "fast_fall_max_speed",
+            config.fast_fall_max_speed,
+            Rule::Positive,
+        ),
+        ("min_walk_speed", config.min_walk_speed, Rule::NonNegative),
+        (
+            "floor_max_angle_degrees",
+            config.floor_max_angle_degrees,
+            Rule::FloorAngle,
+        ),
+        (
+            "floor_snap_length",
+            config.floor_snap_length,
+            Rule::NonNegative,
+        ),
+        (
+            "slope_jump_normal_blend",
+            config.slope_jump_normal_blend,
+            Rule::Fraction,
+        ),
+        (
+            "max_inherited_platform_speed",
+            config.max_inherited_platform_speed,
+            Rule::NonNegative,
+        ),
+        ("climb_speed", config.climb_speed, Rule::Positive),
+        (
+            "wall_slide_max_speed",
+            config.wall_slide_max_speed,
+            Rule::NonNegative,
+        ),
+        (
+            "wall_jump_kick_speed",
+            config.wall_jump_kick_speed,
+            Rule::NonNegative,
+        ),
+        (
+            "wall_jump_lockout_time",
+            config.wall_jump_lockout_time,
+            Rule::NonNegative,
+        ),
+        (
+            "wall_coyote_time",
+            config.wall_coyote_time,
+            Rule::NonNegative,
+        ),
+        (
+            "ledge_regrab_time",
+            config.ledge_regrab_time,
+            Rule::NonNegative,
+        ),
+        (
+            "crouch_speed_multiplier",
+            config.crouch_speed_multiplier,
+            Rule::Fraction,
+        ),
+        ("dash_speed", config.dash_speed, Rule::Positive),
+        ("dash_duration", config.dash_duration, Rule::Positive),
+        ("dash_cooldown", config.dash_cooldown, Rule::NonNegative),
+        ("dash_buffer_time", config.dash_buffer_time, Rule::Positive),
+    ];
+
+    for (key, value, rule) in rules {
+        if !rule.accepts(value) {
+            return Err(ProfileError {
+                key,
+                reason: rule.reason(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_values_the_movement_cannot_use() {
+        let defaults = MovementConfig::default;
+
+        assert!(validate(&defaults()).is_ok());
+        assert_eq!(
+            validate(&MovementConfig {
+                jump_velocity: 300.0,
+                ..defaults()
+            }),
+            Err(ProfileError {
+                key: "jump_velocity",
+                reason: Rule::Negative.reason()
+            })
+        );
+        assert!(
+            validate(&MovementConfig {
+                floor_max_angle_degrees: 90.0,
+                ..defaults()
+            })
+            .is_err()
+        );
+        assert!(
+            validate(&MovementConfig {
+                slope_jump_normal_blend: 1.5,
+                ..defaults()
+            })
+            .is_err()
+        );
+        assert!(
+            validate(&MovementConfig {
+                walk_speed: f32::NAN,
+                ..defaults()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_buffer_times_that_would_disable_jumping_or_dashing() {
+        let defaults = MovementConfig::default;
+
+        assert_eq!(
+            validate(&MovementConfig {
+                jump_buffer_time: 0.0,
+                ..defaults()
+            }),
+            Err(ProfileError {
+                key: "jump_buffer_time",
+                reason: Rule::Positive.reason()
+            })
+        );
+        assert_eq!(
+            validate(&MovementConfig {
+                dash_buffer_time: 0.0,
+                ..defaults()
+            }),
+            Err(ProfileError {
+                key: "dash_buffer_time",
+                reason: Rule::Positive.reason()
+            })
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Movement abilities unlocked by pickups and kept in each slot's profile.
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Ability {
+    DoubleJump,
+    Dash,
+    WallJump,
+    SwimDive,
+}
+
+impl Ability {
+    pub const ALL: [Self; 4] = [Self::DoubleJump, Self::Dash, Self::WallJump, Self::SwimDive];
+
+    /// Stable name used in save files, LDtk fields and by `SaveApi`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DoubleJump => "double_jump",
+            Self::Dash => "dash",
+            Self::WallJump => "wall_jump",
+            Self::SwimDive => "swim_dive",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|ability| ability.name() == name)
+    }
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Set of unlocked abilities; cheap to copy so the player can read it every
+/// frame.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AbilitySet {
+    bits: u32,
+}
+
+impl AbilitySet {
+    pub fn contains(self, ability: Ability) -> bool {
+        self.bits & ability.bit() != 0
+    }
+
+    /// Returns false if `ability` was already in the set.
+    pub fn insert(&mut self, ability: Ability) -> bool {
+        let inserted = !self.contains(ability);
+        self.bits |= ability.bit();
+        inserted
+    }
+
+    pub fn iter(self) -> impl Iterator<Item = Ability> {
+        Ability::ALL
+            .into_iter()
+            .filter(move |ability| self.contains(*ability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_roundtrip() {
+        for ability in Ability::ALL {
+            assert_eq!(Ability::from_name(ability.name()), Some(ability));
+        }
+        assert_eq!(Ability::from_name("glide"), None);
+    }
+
+    #[test]
+    fn set_tracks_inserted_abilities_in_declaration_order() {
+        let mut abilities = AbilitySet::default();
+
+        assert!(abilities.insert(Ability::SwimDive));
+        assert!(abilities.insert(Ability::Dash));
+        assert!(!abilities.insert(Ability::Dash));
+
+        assert!(abilities.contains(Ability::Dash));
+        assert!(!abilities.contains(Ability::WallJump));
+        assert_eq!(
+            abilities.iter().collect::<Vec<_>>(),
+            [Ability::Dash, Ability::SwimDive]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Checksum trailer for save files.
+//!
+//! The trailer is a comment line, `#checksum <fnv1a-64 hex>`, covering every
+//! byte before it. Decoders skip comments, so sealed files stay readable by
+//! tools that ignore the checksum.
+
+const TRAILER_PREFIX: &str = "#checksum ";
+
+#[derive(Debug, PartialEq)]
+pub enum ChecksumStatus<'a> {
+    /// The trailer matches; holds the text without it.
+    Valid(&'a str),
+    /// Written before checksums existed, or cut off before the trailer.
+    Missing(&'a str),
+    Mismatch,
+}
+
+/// Appends the checksum trailer to encoded save text.
+pub fn seal(text: &str) -> String {
+    let mut sealed = text.to_string();
+    if !sealed.is_empty() && !sealed.ends_with('\n') {
+        sealed.push('\n');
+    }
+    let checksum = fnv1a_64(sealed.as_bytes());
+    sealed.push_str(&format!("{TRAILER_PREFIX}{checksum:016x}\n"));
+    sealed
+}
+
+pub fn verify(text: &str) -> ChecksumStatus<'_> {
+    let trimmed = text.trim_end_matches(['\r', '\n']);
+    let body_end = trimmed.rfind('\n').map_or(0, |index| index + 1);
+    let Some(expected) = trimmed[body_end..].strip_prefix(TRAILER_PREFIX) else {
+        return ChecksumStatus::Missing(text);
+    };
+
+    let body = &text[..body_end];
+    match u64::from_str_radix(expected.trim(), 16) {
+        Ok(expected) if expected == fnv1a_64(body.as_bytes()) => ChecksumStatus::Valid(body),
+        _ => ChecksumStatus::Mismatch,
+    }
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "p1proto-save 5\ncheckpoint 1 1 88 120\nexplored 1 1\n";
+
+    #[test]
+    fn sealed_text_verifies_and_strips_the_trailer() {
+        let sealed = seal(TEXT);
+
+        assert!(sealed.starts_with(TEXT));
+        assert_eq!(verify(&sealed), ChecksumStatus::Valid(TEXT));
+    }
+
+    #[test]
+    fn detects_edits_and_truncation() {
+        let sealed = seal(TEXT);
+
+        assert_eq!(
+            verify(&sealed.replace("explored 1 1", "explored 2 1")),
+            ChecksumStatus::Mismatch
+        );
+        let truncated = &sealed[..TEXT.len() - 5];
+        assert_eq!(verify(truncated), ChecksumStatus::Missing(truncated));
+    }
+
+    #[test]
+    fn files_without_a_trailer_are_reported_as_missing() {
+        assert_eq!(verify(TEXT), ChecksumStatus::Missing(TEXT));
+        assert_eq!(verify(""), ChecksumStatus::Missing(""));
+    }
+}
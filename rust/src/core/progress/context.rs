@@ -0,0 +1,59 @@
+//! Shared handle to one progress repository and its subscribers.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use super::ProgressRepository;
+use super::events::{EventBus, ProgressEvent, SubscriptionId};
+
+/// Owns a [`ProgressRepository`] for the code that reads and changes it.
+///
+/// Clones share the same repository and subscribers. Game code uses the
+/// [global](super::global) context; tests and tools can create their own.
+#[derive(Clone, Default)]
+pub struct ProgressContext {
+    repository: Rc<RefCell<ProgressRepository>>,
+    events: Rc<EventBus>,
+}
+
+impl ProgressContext {
+    pub fn read<T>(&self, f: impl FnOnce(&ProgressRepository) -> T) -> T {
+        f(&self.repository.borrow())
+    }
+
+    /// Changes the repository, then notifies subscribers of the events the
+    /// change raised.
+    pub fn write<T>(&self, f: impl FnOnce(&mut ProgressRepository) -> T) -> T {
+        let (result, events) = {
+            let mut repository = self.repository.borrow_mut();
+            let result = f(&mut repository);
+            (result, std::mem::take(&mut repository.events))
+        };
+        self.events.dispatch(events);
+        result
+    }
+
+    /// Calls `callback` for every event of this context until
+    /// [`unsubscribe`](Self::unsubscribe) is called with the returned id.
+    pub fn subscribe(&self, callback: impl FnMut(&ProgressEvent) + 'static) -> SubscriptionId {
+        self.events.subscribe(callback)
+    }
+
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        self.events.unsubscribe(id)
+    }
+}
+
+/// Contexts are equal when they share a repository.
+impl PartialEq for ProgressContext {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.repository, &other.repository)
+    }
+}
+
+impl fmt::Debug for ProgressContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressContext").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,221 @@
+//! Typed per-entity state kept alongside the marked-entity sets.
+//!
+//! Entities store named fields under their `PersistentKey`. Field names and
+//! enum variant names are single whitespace-free tokens so a field survives
+//! the line-based save format.
+
+use godot::prelude::*;
+
+/// One stored field value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EntityStateValue {
+    Bool(bool),
+    Int(i64),
+    /// Enum variant name; see [`EntityState`] for mapping enums.
+    Enum(String),
+    Vector(Vector2),
+    Timestamp(Timestamp),
+}
+
+/// Seconds since the unix epoch.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp(pub i64);
+
+/// Rust types that can be stored as entity state.
+///
+/// Entity-specific enums implement this by mapping each variant to
+/// `EntityStateValue::Enum` with a stable name.
+pub trait EntityState: Sized {
+    fn to_state(&self) -> EntityStateValue;
+    fn from_state(value: &EntityStateValue) -> Option<Self>;
+}
+
+impl EntityState for bool {
+    fn to_state(&self) -> EntityStateValue {
+        EntityStateValue::Bool(*self)
+    }
+
+    fn from_state(value: &EntityStateValue) -> Option<Self> {
+        match value {
+            EntityStateValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl EntityState for i64 {
+    fn to_state(&self) -> EntityStateValue {
+        EntityStateValue::Int(*self)
+    }
+
+    fn from_state(value: &EntityStateValue) -> Option<Self> {
+        match value {
+            EntityStateValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl EntityState for Vector2 {
+    fn to_state(&self) -> EntityStateValue {
+        EntityStateValue::Vector(*self)
+    }
+
+    fn from_state(value: &EntityStateValue) -> Option<Self> {
+        match value {
+            EntityStateValue::Vector(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl EntityState for Timestamp {
+    fn to_state(&self) -> EntityStateValue {
+        EntityStateValue::Timestamp(*self)
+    }
+
+    fn from_state(value: &EntityStateValue) -> Option<Self> {
+        match value {
+            EntityStateValue::Timestamp(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl EntityStateValue {
+    /// Whether the value can be written to a save file.
+    pub fn is_storable(&self) -> bool {
+        match self {
+            Self::Enum(name) => is_state_token(name),
+            Self::Vector(vector) => vector.x.is_finite() && vector.y.is_finite(),
+            Self::Bool(_) | Self::Int(_) | Self::Timestamp(_) => true,
+        }
+    }
+
+    pub(super) fn type_tag(&self) -> &'static str {
+        match self {
+            Self::Bool(_) => "bool",
+            Self::Int(_) => "int",
+            Self::Enum(_) => "enum",
+            Self::Vector(_) => "vec",
+            Self::Timestamp(_) => "time",
+        }
+    }
+
+    pub(super) fn encode_tokens(&self) -> String {
+        match self {
+            Self::Bool(value) => value.to_string(),
+            Self::Int(value) => value.to_string(),
+            Self::Enum(name) => name.clone(),
+            Self::Vector(vector) => format!("{} {}", vector.x, vector.y),
+            Self::Timestamp(Timestamp(seconds)) => seconds.to_string(),
+        }
+    }
+
+    /// Token count of a value with `type_tag`, or `None` for unknown tags.
+    pub(super) fn token_count(type_tag: &str) -> Option<usize> {
+        match type_tag {
+            "bool" | "int" | "enum" | "time" => Some(1),
+            "vec" => Some(2),
+            _ => None,
+        }
+    }
+
+    pub(super) fn decode_tokens(type_tag: &str, tokens: &[&str]) -> Option<Self> {
+        let value = match (type_tag, tokens) {
+            ("bool", [value]) => Self::Bool(value.parse().ok()?),
+            ("int", [value]) => Self::Int(value.parse().ok()?),
+            ("enum", [name]) => Self::Enum(name.to_string()),
+            ("vec", [x, y]) => Self::Vector(Vector2::new(x.parse().ok()?, y.parse().ok()?)),
+            ("time", [seconds]) => Self::Timestamp(Timestamp(seconds.parse().ok()?)),
+            _ => return None,
+        };
+        value.is_storable().then_some(value)
+    }
+}
+
+/// Field and enum names must be one non-empty token without whitespace.
+pub fn is_state_token(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Lever {
+        Up,
+        Down,
+    }
+
+    impl EntityState for Lever {
+        fn to_state(&self) -> EntityStateValue {
+            EntityStateValue::Enum(
+                match self {
+                    Self::Up => "up",
+                    Self::Down => "down",
+                }
+                .to_string(),
+            )
+        }
+
+        fn from_state(value: &EntityStateValue) -> Option<Self> {
+            match value {
+                EntityStateValue::Enum(name) if name == "up" => Some(Self::Up),
+                EntityStateValue::Enum(name) if name == "down" => Some(Self::Down),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn typed_values_roundtrip_and_reject_other_types() {
+        assert_eq!(
+            Lever::from_state(&Lever::Down.to_state()),
+            Some(Lever::Down)
+        );
+        assert_eq!(i64::from_state(&7_i64.to_state()), Some(7));
+        assert_eq!(
+            Timestamp::from_state(&Timestamp(1_760_000_000).to_state()),
+            Some(Timestamp(1_760_000_000))
+        );
+        assert_eq!(bool::from_state(&EntityStateValue::Int(1)), None);
+        assert_eq!(
+            Lever::from_state(&EntityStateValue::Enum("left".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn value_tokens_roundtrip() {
+        let values = [
+            EntityStateValue::Bool(true),
+            EntityStateValue::Int(-42),
+            EntityStateValue::Enum("open".into()),
+            EntityStateValue::Vector(Vector2::new(12.5, -3.0)),
+            EntityStateValue::Timestamp(Timestamp(1_760_000_000)),
+        ];
+
+        for value in values {
+            let encoded = value.encode_tokens();
+            let tokens: Vec<&str> = encoded.split_whitespace().collect();
+            assert_eq!(
+                EntityStateValue::token_count(value.type_tag()),
+                Some(tokens.len())
+            );
+            assert_eq!(
+                EntityStateValue::decode_tokens(value.type_tag(), &tokens),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_values_that_cannot_be_saved() {
+        assert!(!EntityStateValue::Enum("two words".into()).is_storable());
+        assert!(!EntityStateValue::Vector(Vector2::new(f32::NAN, 0.0)).is_storable());
+        assert_eq!(EntityStateValue::decode_tokens("vec", &["1", "inf"]), None);
+        assert_eq!(EntityStateValue::decode_tokens("color", &["red"]), None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Change notifications for the progress repository.
+//!
+//! Mutations queue events while the repository is borrowed; the owning
+//! context dispatches them once the borrow ends, so subscribers may read or
+//! update progress from their callbacks.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use super::abilities::Ability;
+use super::{PersistentEntityKind, PersistentKey, SaveSlot, SaveSnapshot};
+use crate::core::world::RoomId;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressEvent {
+    /// A key, lock, star or ability pickup was marked in the active slot, pending or not.
+    EntityMarked {
+        kind: PersistentEntityKind,
+        key: PersistentKey,
+    },
+    /// Sent after the matching `EntityMarked`, with the new star count.
+    StarCollected {
+        key: PersistentKey,
+        star_count: usize,
+    },
+    AbilityUnlocked(Ability),
+    RoomExplored(RoomId),
+    CheckpointSaved {
+        slot: SaveSlot,
+        snapshot: SaveSnapshot,
+    },
+    /// The active slot's progress was replaced: read from disk, reloaded to
+    /// its checkpoint, or started over. Cached views should re-read it.
+    SlotLoaded(SaveSlot),
+    /// `to` was overwritten with a copy of `from`.
+    SlotCopied {
+        from: SaveSlot,
+        to: SaveSlot,
+    },
+    SlotDeleted(SaveSlot),
+}
+
+pub type SubscriptionId = u64;
+
+type Subscriber = Box<dyn FnMut(&ProgressEvent)>;
+
+#[derive(Default)]
+struct Subscribers {
+    next_id: SubscriptionId,
+    callbacks: Vec<(SubscriptionId, Subscriber)>,
+    /// Set while callbacks are taken out and running.
+    dispatching: bool,
+    /// Events raised by callbacks wait here until the current ones finish.
+    queued_events: VecDeque<ProgressEvent>,
+    /// Ids unsubscribed while their callbacks were taken out for dispatch.
+    removed_during_dispatch: Vec<SubscriptionId>,
+}
+
+/// Subscribers of one [`ProgressContext`](super::ProgressContext).
+#[derive(Default)]
+pub(super) struct EventBus {
+    subscribers: RefCell<Subscribers>,
+}
+
+impl EventBus {
+    pub(super) fn subscribe(
+        &self,
+        callback: impl FnMut(&ProgressEvent) + 'static,
+    ) -> SubscriptionId {
+        let mut subscribers = self.subscribers.borrow_mut();
+        subscribers.next_id += 1;
+        let id = subscribers.next_id;
+        subscribers.callbacks.push((id, Box::new(callback)));
+        id
+    }
+
+    pub(super) fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.subscribers.borrow_mut();
+        let count = subscribers.callbacks.len();
+        subscribers
+            .callbacks
+            .retain(|(subscriber_id, _)| *subscriber_id != id);
+        if subscribers.callbacks.len() < count {
+            return true;
+        }
+        if subscribers.dispatching && id <= subscribers.next_id {
+            subscribers.removed_during_dispatch.push(id);
+            return true;
+        }
+        false
+    }
+
+    pub(super) fn dispatch(&self, events: Vec<ProgressEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        // Callbacks run outside the borrow so they can subscribe, unsubscribe or
+        // change progress; events they raise are delivered after the current ones.
+        let mut callbacks = {
+            let mut subscribers = self.subscribers.borrow_mut();
+            subscribers.queued_events.extend(events);
+            if subscribers.dispatching {
+                return;
+            }
+            subscribers.dispatching = true;
+            std::mem::take(&mut subscribers.callbacks)
+        };
+
+        while let Some(event) = self.next_event(&mut callbacks) {
+            for (_, callback) in &mut callbacks {
+                callback(&event);
+            }
+        }
+
+        let mut subscribers = self.subscribers.borrow_mut();
+        // Subscribers added during dispatch go after the existing ones.
+        callbacks.append(&mut subscribers.callbacks);
+        subscribers.callbacks = callbacks;
+        subscribers.dispatching = false;
+    }
+
+    fn next_event(
+        &self,
+        callbacks: &mut Vec<(SubscriptionId, Subscriber)>,
+    ) -> Option<ProgressEvent> {
+        let mut subscribers = self.subscribers.borrow_mut();
+        let removed = std::mem::take(&mut subscribers.removed_during_dispatch);
+        callbacks.retain(|(id, _)| !removed.contains(id));
+        subscribers.queued_events.pop_front()
+    }
+}
+
+/// Calls `callback` for every event of the [global](super::global) progress
+/// context until [`unsubscribe`] is called with the returned id.
+pub fn subscribe(callback: impl FnMut(&ProgressEvent) + 'static) -> SubscriptionId {
+    super::global().subscribe(callback)
+}
+
+pub fn unsubscribe(id: SubscriptionId) -> bool {
+    super::global().unsubscribe(id)
+}
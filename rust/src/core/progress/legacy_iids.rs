@@ -0,0 +1,243 @@
+//! Table from legacy `(room, position)` keys to LDtk entity IIDs.
+//!
+//! `cargo xtask ldtk-iids` generates the table from `tilemap.ldtk`. Each
+//! line reads `<room_x> <room_y> <x> <y> <iid>`, optionally followed by a
+//! `#` comment naming the entity.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::migration::LegacyIidLookup;
+use super::{PersistentEntityKind, PersistentKey, ProgressProfile, SaveSnapshot};
+use crate::core::world::RoomId;
+
+const COMMENT_PREFIX: char = '#';
+
+#[derive(Debug, Default)]
+pub struct LegacyIidTable {
+    iids: HashMap<(RoomId, (i32, i32)), String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LegacyIidTableError {
+    pub line: usize,
+    pub content: String,
+}
+
+impl fmt::Display for LegacyIidTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "malformed legacy IID entry on line {}: {}",
+            self.line, self.content
+        )
+    }
+}
+
+/// Outcome of resolving one slot's legacy keys against the table.
+#[derive(Debug, Default, PartialEq)]
+pub struct LegacyKeyReport {
+    pub resolved: usize,
+    pub unresolved: Vec<PersistentKey>,
+}
+
+impl LegacyIidTable {
+    pub fn parse(text: &str) -> Result<Self, LegacyIidTableError> {
+        let mut table = Self::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let content = line
+                .split_once(COMMENT_PREFIX)
+                .map_or(line, |(content, _)| content)
+                .trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let tokens: Vec<&str> = content.split_whitespace().collect();
+            let entry = match tokens.as_slice() {
+                [x, y, px, py, iid] => parse_entry(x, y, px, py).map(|key| (key, iid)),
+                _ => None,
+            };
+            let Some((key, iid)) = entry else {
+                return Err(LegacyIidTableError {
+                    line: index + 1,
+                    content: line.trim().to_string(),
+                });
+            };
+            table.iids.insert(key, iid.to_string());
+        }
+
+        Ok(table)
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.iids.len()
+    }
+
+    /// Rewrites every legacy key in a decoded slot that the table knows.
+    pub fn resolve_slot(
+        &self,
+        checkpoint: Option<&mut SaveSnapshot>,
+        profile: &mut ProgressProfile,
+    ) -> LegacyKeyReport {
+        let mut report = LegacyKeyReport::default();
+
+        if let Some(key) = checkpoint.and_then(|snapshot| snapshot.checkpoint_key.as_mut()) {
+            self.resolve_key(key, &mut report);
+        }
+
+        for kind in PersistentEntityKind::ALL {
+            let set = profile.entity_set_mut(kind);
+            let legacy: Vec<PersistentKey> = set
+                .iter()
+                .filter(|key| matches!(key, PersistentKey::Legacy { .. }))
+                .cloned()
+                .collect();
+
+            for mut key in legacy {
+                set.remove(&key);
+                self.resolve_key(&mut key, &mut report);
+                set.insert(key);
+            }
+        }
+
+        let legacy: Vec<PersistentKey> = profile
+            .entity_state
+            .keys()
+            .filter(|key| matches!(key, PersistentKey::Legacy { .. }))
+            .cloned()
+            .collect();
+        for key in legacy {
+            let Some(fields) = profile.entity_state.remove(&key) else {
+                continue;
+            };
+            let mut resolved = key;
+            self.resolve_key(&mut resolved, &mut report);
+            profile
+                .entity_state
+                .entry(resolved)
+                .or_default()
+                .extend(fields);
+        }
+
+        report
+    }
+
+    fn resolve_key(&self, key: &mut PersistentKey, report: &mut LegacyKeyReport) {
+        let PersistentKey::Legacy { room, position } = key else {
+            return;
+        };
+
+        match self.lookup(*room, *position) {
+            Some(iid) => {
+                *key = PersistentKey::Explicit(iid.to_string());
+                report.resolved += 1;
+            }
+            None => report.unresolved.push(key.clone()),
+        }
+    }
+}
+
+impl LegacyIidLookup for LegacyIidTable {
+    fn lookup(&self, room: RoomId, position: (i32, i32)) -> Option<&str> {
+        self.iids.get(&(room, position)).map(String::as_str)
+    }
+}
+
+fn parse_entry(x: &str, y: &str, px: &str, py: &str) -> Option<(RoomId, (i32, i32))> {
+    Some((
+        RoomId::new(x.parse().ok()?, y.parse().ok()?),
+        (px.parse().ok()?, py.parse().ok()?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::core::progress::entity_state::EntityStateValue;
+
+    const TABLE: &str = "\
+# Generated by `cargo xtask ldtk-iids` from tilemap.ldtk.
+0 1 304 216 fa770b60-d380-11f0-89c2-132b0ea3b98c # PlainLock
+1 1 88 120 8e0e9f10-fa90-11f0-943d-11ec93e38c1d
+";
+
+    fn legacy(x: i32, y: i32, px: i32, py: i32) -> PersistentKey {
+        PersistentKey::Legacy {
+            room: RoomId::new(x, y),
+            position: (px, py),
+        }
+    }
+
+    #[test]
+    fn parses_entries_and_skips_comments() {
+        let table = LegacyIidTable::parse(TABLE).expect("parses");
+
+        assert_eq!(table.entry_count(), 2);
+        assert_eq!(
+            table.lookup(RoomId::new(0, 1), (304, 216)),
+            Some("fa770b60-d380-11f0-89c2-132b0ea3b98c")
+        );
+        assert_eq!(table.lookup(RoomId::new(0, 1), (0, 0)), None);
+    }
+
+    #[test]
+    fn reports_malformed_line() {
+        assert_eq!(
+            LegacyIidTable::parse("0 1 304 216 a\n0 1 x 216 b\n").err(),
+            Some(LegacyIidTableError {
+                line: 2,
+                content: "0 1 x 216 b".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn resolves_known_keys_and_reports_the_rest() {
+        let table = LegacyIidTable::parse(TABLE).unwrap();
+        let mut checkpoint = SaveSnapshot::with_checkpoint_key(
+            RoomId::new(1, 1),
+            Vector2::new(88.0, 120.0),
+            legacy(1, 1, 88, 120),
+        );
+        let mut profile = ProgressProfile::default();
+        profile.mark_entity_key(PersistentEntityKind::Lock, legacy(0, 1, 304, 216));
+        profile.mark_entity_key(PersistentEntityKind::Key, legacy(2, 1, 40, 56));
+        profile.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star".to_string()),
+        );
+        profile.set_entity_state(legacy(0, 1, 304, 216), "open", EntityStateValue::Bool(true));
+
+        let report = table.resolve_slot(Some(&mut checkpoint), &mut profile);
+
+        assert_eq!(
+            report,
+            LegacyKeyReport {
+                resolved: 3,
+                unresolved: vec![legacy(2, 1, 40, 56)],
+            }
+        );
+        assert_eq!(
+            checkpoint.checkpoint_key,
+            Some(PersistentKey::Explicit(
+                "8e0e9f10-fa90-11f0-943d-11ec93e38c1d".to_string()
+            ))
+        );
+        assert!(profile.has_entity_key(
+            PersistentEntityKind::Lock,
+            &PersistentKey::Explicit("fa770b60-d380-11f0-89c2-132b0ea3b98c".to_string()),
+        ));
+        assert!(profile.has_entity_key(PersistentEntityKind::Key, &legacy(2, 1, 40, 56)));
+        assert_eq!(
+            profile.entity_state(
+                &PersistentKey::Explicit("fa770b60-d380-11f0-89c2-132b0ea3b98c".to_string()),
+                "open"
+            ),
+            Some(&EntityStateValue::Bool(true))
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Step-by-step upgrades for save documents written by older builds.
+//!
+//! Migrations work on tokenized records instead of typed progress so they
+//! can still read layouts that the current decoder no longer understands.
+
+use super::save_file::{EXPLICIT_KEY_TAG, LEGACY_KEY_TAG, SaveFileError};
+use crate::core::world::RoomId;
+
+/// Schema version written by this build.
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// `MIGRATIONS[n]` upgrades a document from version `n + 1` to `n + 2`.
+const MIGRATIONS: [Migration; 5] = [
+    add_play_metadata,
+    resolve_legacy_keys,
+    add_entity_state,
+    add_statistics,
+    add_abilities,
+];
+
+/// Token count of an encoded `legacy <rx> <ry> <px> <py>` key.
+const LEGACY_KEY_TOKENS: usize = 5;
+
+type Migration = fn(&mut SaveDocument, &MigrationContext);
+
+/// Resolves `PersistentKey::Legacy` coordinates to LDtk entity IIDs.
+pub trait LegacyIidLookup {
+    fn lookup(&self, room: RoomId, position: (i32, i32)) -> Option<&str>;
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct MigrationContext<'a> {
+    /// Without a lookup, legacy keys are carried over unchanged.
+    pub legacy_iids: Option<&'a dyn LegacyIidLookup>,
+}
+
+pub struct SaveRecord {
+    pub line: usize,
+    pub tokens: Vec<String>,
+}
+
+pub struct SaveDocument {
+    pub version: u32,
+    pub records: Vec<SaveRecord>,
+}
+
+/// Runs every migration between the document's version and `LATEST_VERSION`.
+pub fn upgrade(
+    document: &mut SaveDocument,
+    context: &MigrationContext,
+) -> Result<(), SaveFileError> {
+    if !(1..=LATEST_VERSION).contains(&document.version) {
+        return Err(SaveFileError::UnsupportedVersion(document.version));
+    }
+
+    while document.version < LATEST_VERSION {
+        let migration = MIGRATIONS[document.version as usize - 1];
+        migration(document, context);
+        document.version += 1;
+    }
+    Ok(())
+}
+
+/// v1 -> v2: playtime and last-played records became optional additions, so
+/// existing records carry over untouched.
+fn add_play_metadata(_document: &mut SaveDocument, _context: &MigrationContext) {}
+
+/// v2 -> v3: rewrites legacy entity and checkpoint keys to explicit LDtk IIDs
+/// wherever the lookup knows the entity.
+fn resolve_legacy_keys(document: &mut SaveDocument, context: &MigrationContext) {
+    let Some(lookup) = context.legacy_iids else {
+        return;
+    };
+
+    for record in &mut document.records {
+        let Some(key_start) = record.tokens.len().checked_sub(LEGACY_KEY_TOKENS) else {
+            continue;
+        };
+        let Some(iid) = legacy_key_iid(&record.tokens[key_start..], lookup) else {
+            continue;
+        };
+
+        record.tokens.truncate(key_start);
+        record.tokens.push(EXPLICIT_KEY_TAG.to_string());
+        record.tokens.push(iid);
+    }
+}
+
+/// v3 -> v4: typed `state` records were added; older documents have none.
+fn add_entity_state(_document: &mut SaveDocument, _context: &MigrationContext) {}
+
+/// v4 -> v5: play statistics records were added; older documents start at zero.
+fn add_statistics(_document: &mut SaveDocument, _context: &MigrationContext) {}
+
+/// v5 -> v6: ability unlocks and ability pickups were added; older documents
+/// have neither.
+fn add_abilities(_document: &mut SaveDocument, _context: &MigrationContext) {}
+
+fn legacy_key_iid(tokens: &[String], lookup: &dyn LegacyIidLookup) -> Option<String> {
+    let [tag, x, y, px, py] = tokens else {
+        return None;
+    };
+    if tag != LEGACY_KEY_TAG {
+        return None;
+    }
+
+    let room = RoomId::new(x.parse().ok()?, y.parse().ok()?);
+    let position = (px.parse().ok()?, py.parse().ok()?);
+    lookup.lookup(room, position).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::progress::save_file::{decode, encode};
+    use crate::core::progress::{PersistentEntityKind, PersistentKey};
+
+    const SAVE_V1: &str = include_str!("fixtures/save_v1.txt");
+    const SAVE_V2: &str = include_str!("fixtures/save_v2.txt");
+    const SAVE_V1_MIGRATED: &str = include_str!("fixtures/save_v1_migrated.txt");
+
+    impl LegacyIidLookup for HashMap<(RoomId, (i32, i32)), String> {
+        fn lookup(&self, room: RoomId, position: (i32, i32)) -> Option<&str> {
+            self.get(&(room, position)).map(String::as_str)
+        }
+    }
+
+    fn fixture_iids() -> HashMap<(RoomId, (i32, i32)), String> {
+        HashMap::from([
+            (
+                (RoomId::new(0, 1), (152, 200)),
+                "1c5b3a60-fa90-11f0-943d-11ec93e38c1d".to_string(),
+            ),
+            (
+                (RoomId::new(1, 1), (88, 120)),
+                "8e0e9f10-fa90-11f0-943d-11ec93e38c1d".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn upgrades_v1_and_resolves_known_legacy_keys_only() {
+        let iids = fixture_iids();
+        let context = MigrationContext {
+            legacy_iids: Some(&iids),
+        };
+
+        let decoded = decode(SAVE_V1, &context).expect("v1 fixture decodes");
+
+        assert_eq!(
+            encode(decoded.checkpoint.as_ref(), &decoded.profile),
+            SAVE_V1_MIGRATED
+        );
+    }
+
+    #[test]
+    fn keeps_legacy_keys_without_lookup() {
+        let decoded = decode(SAVE_V2, &MigrationContext::default()).expect("v2 decodes");
+
+        assert_eq!(decoded.profile.playtime_seconds(), 754.5);
+        assert!(decoded.profile.has_entity_key(
+            PersistentEntityKind::Key,
+            &PersistentKey::Legacy {
+                room: RoomId::new(0, 1),
+                position: (152, 200),
+            },
+        ));
+    }
+
+    #[test]
+    fn rejects_versions_outside_the_chain() {
+        for version in [0, LATEST_VERSION + 1] {
+            let mut document = SaveDocument {
+                version,
+                records: Vec::new(),
+            };
+            assert_eq!(
+                upgrade(&mut document, &MigrationContext::default()).err(),
+                Some(SaveFileError::UnsupportedVersion(version))
+            );
+        }
+    }
+}
@@ -0,0 +1,1329 @@
+use godot::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use super::world::RoomId;
+
+pub mod abilities;
+pub mod checksum;
+mod context;
+pub mod entity_state;
+pub mod events;
+pub mod legacy_iids;
+pub mod migration;
+pub mod save_file;
+pub mod statistics;
+pub mod transfer;
+
+use abilities::{Ability, AbilitySet};
+pub use context::ProgressContext;
+use entity_state::{EntityStateValue, is_state_token};
+use events::ProgressEvent;
+use legacy_iids::{LegacyIidTable, LegacyKeyReport};
+use migration::MigrationContext;
+use save_file::{SaveFileContents, SaveFileError};
+use statistics::{DeathCause, PlayStatistics};
+use transfer::ImportError;
+
+pub const DEFAULT_SAVE_SLOT: usize = 0;
+pub const SAVE_SLOT_COUNT: usize = 3;
+
+pub type SaveSlot = usize;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PersistentKey {
+    Explicit(String),
+    Legacy { room: RoomId, position: (i32, i32) },
+}
+
+/// Same tokens as in save files, e.g. `iid <uuid>`.
+impl fmt::Display for PersistentKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&save_file::encode_key(self))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PersistentEntityKind {
+    Key,
+    Lock,
+    Star,
+    AbilityPickup,
+}
+
+impl PersistentEntityKind {
+    pub const ALL: [Self; 4] = [Self::Key, Self::Lock, Self::Star, Self::AbilityPickup];
+
+    /// Stable name used in save files and by `SaveApi`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Key => "key",
+            Self::Lock => "lock",
+            Self::Star => "star",
+            Self::AbilityPickup => "ability_pickup",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Ability pickups are kept at once, like the abilities they grant, so a
+    /// reload cannot take an unlock back.
+    fn default_commit_policy(self) -> CommitPolicy {
+        match self {
+            Self::AbilityPickup => CommitPolicy::Immediate,
+            Self::Key | Self::Lock | Self::Star => CommitPolicy::Transactional,
+        }
+    }
+}
+
+/// When a mark of some entity kind reaches the slot's profile.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CommitPolicy {
+    /// Held as pending until the next checkpoint and dropped when the player
+    /// reloads the last checkpoint instead.
+    #[default]
+    Transactional,
+    /// Written to the profile as soon as it is made.
+    Immediate,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveSnapshot {
+    pub room: RoomId,
+    pub position: Vector2,
+    checkpoint_key: Option<PersistentKey>,
+}
+
+impl SaveSnapshot {
+    pub fn new(room: RoomId, position: Vector2) -> Self {
+        Self {
+            room,
+            position,
+            checkpoint_key: None,
+        }
+    }
+
+    pub fn with_checkpoint_key(
+        room: RoomId,
+        position: Vector2,
+        checkpoint_key: PersistentKey,
+    ) -> Self {
+        Self {
+            room,
+            position,
+            checkpoint_key: Some(checkpoint_key),
+        }
+    }
+
+    pub fn matches_checkpoint(
+        &self,
+        room: RoomId,
+        position: Vector2,
+        match_epsilon: f32,
+        checkpoint_key: Option<&PersistentKey>,
+    ) -> bool {
+        if self.room != room {
+            return false;
+        }
+
+        if let (Some(saved_key), Some(current_key)) = (self.checkpoint_key.as_ref(), checkpoint_key)
+        {
+            return saved_key == current_key;
+        }
+
+        position.distance_to(self.position) <= match_epsilon
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ProgressProfile {
+    unlocked_locks: HashSet<PersistentKey>,
+    collected_keys: HashSet<PersistentKey>,
+    collected_stars: HashSet<PersistentKey>,
+    collected_ability_pickups: HashSet<PersistentKey>,
+    abilities: AbilitySet,
+    explored_rooms: HashSet<RoomId>,
+    entity_state: HashMap<PersistentKey, BTreeMap<String, EntityStateValue>>,
+    playtime_seconds: f64,
+    last_played_unix: Option<i64>,
+    statistics: PlayStatistics,
+}
+
+impl ProgressProfile {
+    fn entity_set(&self, kind: PersistentEntityKind) -> &HashSet<PersistentKey> {
+        match kind {
+            PersistentEntityKind::Key => &self.collected_keys,
+            PersistentEntityKind::Lock => &self.unlocked_locks,
+            PersistentEntityKind::Star => &self.collected_stars,
+            PersistentEntityKind::AbilityPickup => &self.collected_ability_pickups,
+        }
+    }
+
+    fn entity_set_mut(&mut self, kind: PersistentEntityKind) -> &mut HashSet<PersistentKey> {
+        match kind {
+            PersistentEntityKind::Key => &mut self.collected_keys,
+            PersistentEntityKind::Lock => &mut self.unlocked_locks,
+            PersistentEntityKind::Star => &mut self.collected_stars,
+            PersistentEntityKind::AbilityPickup => &mut self.collected_ability_pickups,
+        }
+    }
+
+    pub fn mark_entity_key(&mut self, kind: PersistentEntityKind, key: PersistentKey) -> bool {
+        self.entity_set_mut(kind).insert(key)
+    }
+
+    pub fn has_entity_key(&self, kind: PersistentEntityKind, key: &PersistentKey) -> bool {
+        self.entity_set(kind).contains(key)
+    }
+
+    pub fn entity_state(&self, key: &PersistentKey, field: &str) -> Option<&EntityStateValue> {
+        self.entity_state.get(key)?.get(field)
+    }
+
+    /// Stores `value` under `field`. Rejects names and values that the save
+    /// file cannot represent.
+    pub fn set_entity_state(
+        &mut self,
+        key: PersistentKey,
+        field: &str,
+        value: EntityStateValue,
+    ) -> bool {
+        if !is_state_token(field) || !value.is_storable() {
+            return false;
+        }
+        self.entity_state
+            .entry(key)
+            .or_default()
+            .insert(field.to_string(), value);
+        true
+    }
+
+    pub fn clear_entity_state(&mut self, key: &PersistentKey, field: &str) -> bool {
+        let Some(fields) = self.entity_state.get_mut(key) else {
+            return false;
+        };
+        let removed = fields.remove(field).is_some();
+        if fields.is_empty() {
+            self.entity_state.remove(key);
+        }
+        removed
+    }
+
+    pub fn unlock_ability(&mut self, ability: Ability) -> bool {
+        self.abilities.insert(ability)
+    }
+
+    pub fn abilities(&self) -> AbilitySet {
+        self.abilities
+    }
+
+    pub fn mark_room_explored(&mut self, room: RoomId) -> bool {
+        self.explored_rooms.insert(room)
+    }
+
+    pub fn is_room_explored(&self, room: RoomId) -> bool {
+        self.explored_rooms.contains(&room)
+    }
+
+    pub fn list_explored_rooms(&self) -> Vec<RoomId> {
+        self.explored_rooms.iter().copied().collect()
+    }
+
+    pub fn explored_room_count(&self) -> usize {
+        self.explored_rooms.len()
+    }
+
+    pub fn star_count(&self) -> usize {
+        self.collected_stars.len()
+    }
+
+    pub fn add_playtime(&mut self, seconds: f64) {
+        if seconds.is_finite() && seconds > 0.0 {
+            self.playtime_seconds += seconds;
+        }
+    }
+
+    pub fn playtime_seconds(&self) -> f64 {
+        self.playtime_seconds
+    }
+
+    pub fn set_last_played(&mut self, unix_time: i64) {
+        self.last_played_unix = Some(unix_time);
+    }
+
+    pub fn last_played_unix(&self) -> Option<i64> {
+        self.last_played_unix
+    }
+
+    pub fn statistics(&self) -> &PlayStatistics {
+        &self.statistics
+    }
+
+    pub fn statistics_mut(&mut self) -> &mut PlayStatistics {
+        &mut self.statistics
+    }
+
+    /// Marks a star and records when it was collected.
+    fn collect_star(&mut self, key: PersistentKey, playtime_seconds: f64) -> bool {
+        self.statistics
+            .record_star_collected(key.clone(), playtime_seconds);
+        self.mark_entity_key(PersistentEntityKind::Star, key)
+    }
+
+    pub fn reset(&mut self) {
+        self.reset_run();
+        self.playtime_seconds = 0.0;
+        self.statistics = PlayStatistics::default();
+    }
+
+    /// Clears what the run collected and explored but keeps the playtime and
+    /// statistics recorded so far.
+    pub fn reset_run(&mut self) {
+        self.unlocked_locks.clear();
+        self.collected_keys.clear();
+        self.collected_stars.clear();
+        self.collected_ability_pickups.clear();
+        self.abilities = AbilitySet::default();
+        self.explored_rooms.clear();
+        self.entity_state.clear();
+        self.last_played_unix = None;
+    }
+}
+
+/// What the slot picker shows for one save slot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotSummary {
+    pub slot: SaveSlot,
+    pub room: Option<RoomId>,
+    pub star_count: usize,
+    pub explored_room_count: usize,
+    pub last_played_unix: Option<i64>,
+    pub playtime_seconds: f64,
+}
+
+impl SlotSummary {
+    pub fn has_save(&self) -> bool {
+        self.room.is_some()
+    }
+}
+
+/// Checkpoint and progress owned by one save slot.
+#[derive(Clone, Default)]
+struct SlotState {
+    checkpoint: Option<SaveSnapshot>,
+    profile: ProgressProfile,
+}
+
+#[derive(Default)]
+pub struct ProgressRepository {
+    slots: Vec<SlotState>,
+    pending_load_slot: Option<SaveSlot>,
+    active_slot: SaveSlot,
+    /// Transactional marks made in the active slot since its last checkpoint,
+    /// with the playtime they were made at.
+    pending_marks: HashMap<(PersistentEntityKind, PersistentKey), f64>,
+    commit_policies: HashMap<PersistentEntityKind, CommitPolicy>,
+    /// Raised by mutations and dispatched by the owning context once the repository
+    /// is no longer borrowed.
+    events: Vec<ProgressEvent>,
+}
+
+impl ProgressRepository {
+    fn ensure_slot(&mut self, slot: SaveSlot) -> &mut SlotState {
+        if self.slots.len() <= slot {
+            self.slots.resize_with(slot + 1, SlotState::default);
+        }
+        &mut self.slots[slot]
+    }
+
+    fn checkpoint(&self, slot: SaveSlot) -> Option<&SaveSnapshot> {
+        self.slots
+            .get(slot)
+            .and_then(|state| state.checkpoint.as_ref())
+    }
+
+    fn active_profile(&self) -> Option<&ProgressProfile> {
+        self.slots.get(self.active_slot).map(|state| &state.profile)
+    }
+
+    fn active_profile_mut(&mut self) -> &mut ProgressProfile {
+        let slot = self.active_slot;
+        &mut self.ensure_slot(slot).profile
+    }
+
+    pub fn active_slot(&self) -> SaveSlot {
+        self.active_slot
+    }
+
+    /// Clears `slot` and makes it the target of subsequent progress updates.
+    pub fn start_new_game(&mut self, slot: SaveSlot) {
+        self.restart_slot(slot, ProgressProfile::reset);
+    }
+
+    /// Like `start_new_game`, but keeps the slot's playtime and statistics,
+    /// for a run that ended before reaching its first checkpoint.
+    pub fn restart_run(&mut self, slot: SaveSlot) {
+        self.restart_slot(slot, ProgressProfile::reset_run);
+    }
+
+    fn restart_slot(&mut self, slot: SaveSlot, reset: fn(&mut ProgressProfile)) {
+        let state = self.ensure_slot(slot);
+        state.checkpoint = None;
+        reset(&mut state.profile);
+        self.active_slot = slot;
+        self.pending_load_slot = None;
+        self.discard_pending();
+        self.events.push(ProgressEvent::SlotLoaded(slot));
+    }
+
+    /// Overwrites `to` with a copy of `from`. Fails when `from` has no save.
+    pub fn copy_slot(&mut self, from: SaveSlot, to: SaveSlot) -> bool {
+        if from == to || !self.has_save(from) {
+            return false;
+        }
+        let state = self.slots[from].clone();
+        *self.ensure_slot(to) = state;
+        if self.pending_load_slot == Some(to) {
+            self.pending_load_slot = None;
+        }
+        if self.active_slot == to {
+            self.discard_pending();
+        }
+        self.events.push(ProgressEvent::SlotCopied { from, to });
+        true
+    }
+
+    pub fn delete_slot(&mut self, slot: SaveSlot) {
+        if let Some(state) = self.slots.get_mut(slot) {
+            *state = SlotState::default();
+        }
+        if self.pending_load_slot == Some(slot) {
+            self.pending_load_slot = None;
+        }
+        if self.active_slot == slot {
+            self.discard_pending();
+        }
+        self.events.push(ProgressEvent::SlotDeleted(slot));
+    }
+
+    pub fn slot_summary(&self, slot: SaveSlot) -> SlotSummary {
+        let state = self.slots.get(slot);
+        let profile = state.map(|state| &state.profile);
+        SlotSummary {
+            slot,
+            room: state
+                .and_then(|state| state.checkpoint.as_ref())
+                .map(|snapshot| snapshot.room),
+            star_count: profile.map_or(0, ProgressProfile::star_count),
+            explored_room_count: profile.map_or(0, ProgressProfile::explored_room_count),
+            last_played_unix: profile.and_then(ProgressProfile::last_played_unix),
+            playtime_seconds: profile.map_or(0.0, ProgressProfile::playtime_seconds),
+        }
+    }
+
+    /// The saved slot played most recently; ties go to the lowest slot.
+    pub fn most_recent_slot(&self) -> Option<SaveSlot> {
+        (0..self.slots.len())
+            .filter(|slot| self.has_save(*slot))
+            .max_by_key(|slot| (self.slots[*slot].profile.last_played_unix, Reverse(*slot)))
+    }
+
+    pub fn add_playtime(&mut self, seconds: f64) {
+        self.active_profile_mut().add_playtime(seconds);
+    }
+
+    pub fn add_room_time(&mut self, room: RoomId, seconds: f64) {
+        self.active_profile_mut()
+            .statistics_mut()
+            .add_room_time(room, seconds);
+    }
+
+    pub fn record_death(&mut self, room: RoomId, cause: DeathCause) {
+        self.active_profile_mut()
+            .statistics_mut()
+            .record_death(room, cause);
+    }
+
+    pub fn record_jump(&mut self) {
+        self.active_profile_mut().statistics_mut().record_jump();
+    }
+
+    pub fn record_room_transition(&mut self) {
+        self.active_profile_mut()
+            .statistics_mut()
+            .record_room_transition();
+    }
+
+    /// Statistics of the active slot; its playtime is `playtime_seconds`.
+    pub fn statistics(&self) -> PlayStatistics {
+        self.active_profile()
+            .map(|profile| profile.statistics().clone())
+            .unwrap_or_default()
+    }
+
+    pub fn playtime_seconds(&self) -> f64 {
+        self.active_profile()
+            .map_or(0.0, ProgressProfile::playtime_seconds)
+    }
+
+    pub fn touch_slot(&mut self, slot: SaveSlot, unix_time: i64) {
+        self.ensure_slot(slot).profile.set_last_played(unix_time);
+    }
+
+    pub fn save_checkpoint(
+        &mut self,
+        slot: SaveSlot,
+        room: RoomId,
+        position: Vector2,
+        checkpoint_key: Option<PersistentKey>,
+    ) -> SaveSnapshot {
+        let snapshot = checkpoint_key
+            .map(|key| SaveSnapshot::with_checkpoint_key(room, position, key))
+            .unwrap_or_else(|| SaveSnapshot::new(room, position));
+        self.commit_pending();
+        self.active_slot = slot;
+        self.ensure_slot(slot).checkpoint = Some(snapshot.clone());
+        self.events.push(ProgressEvent::CheckpointSaved {
+            slot,
+            snapshot: snapshot.clone(),
+        });
+        snapshot
+    }
+
+    pub fn commit_policy(&self, kind: PersistentEntityKind) -> CommitPolicy {
+        self.commit_policies
+            .get(&kind)
+            .copied()
+            .unwrap_or(kind.default_commit_policy())
+    }
+
+    /// Switching a kind to `Immediate` commits its pending marks right away.
+    pub fn set_commit_policy(&mut self, kind: PersistentEntityKind, policy: CommitPolicy) {
+        self.commit_policies.insert(kind, policy);
+        if policy == CommitPolicy::Immediate {
+            let (immediate, pending) = std::mem::take(&mut self.pending_marks)
+                .into_iter()
+                .partition(|((pending_kind, _), _)| *pending_kind == kind);
+            self.pending_marks = pending;
+            self.apply_marks(immediate);
+        }
+    }
+
+    /// Moves pending marks into the active slot's profile.
+    fn commit_pending(&mut self) {
+        let pending = std::mem::take(&mut self.pending_marks);
+        self.apply_marks(pending);
+    }
+
+    /// Drops marks made since the last checkpoint.
+    fn discard_pending(&mut self) {
+        self.pending_marks.clear();
+    }
+
+    fn apply_marks(&mut self, marks: HashMap<(PersistentEntityKind, PersistentKey), f64>) {
+        let profile = self.active_profile_mut();
+        for ((kind, key), playtime_seconds) in marks {
+            match kind {
+                PersistentEntityKind::Star => profile.collect_star(key, playtime_seconds),
+                _ => profile.mark_entity_key(kind, key),
+            };
+        }
+    }
+
+    pub fn peek_checkpoint(&self, slot: SaveSlot) -> Option<SaveSnapshot> {
+        self.checkpoint(slot).cloned()
+    }
+
+    pub fn has_save(&self, slot: SaveSlot) -> bool {
+        self.checkpoint(slot).is_some()
+    }
+
+    /// Returning to a checkpoint, whether by dying or continuing from the
+    /// menu, discards anything not committed since it was saved.
+    pub fn queue_load(&mut self, slot: SaveSlot) -> bool {
+        if self.has_save(slot) {
+            self.pending_load_slot = Some(slot);
+            self.active_slot = slot;
+            self.discard_pending();
+            self.events.push(ProgressEvent::SlotLoaded(slot));
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn take_pending_load(&mut self) -> Option<SaveSnapshot> {
+        self.pending_load_slot
+            .take()
+            .and_then(|slot| self.peek_checkpoint(slot))
+    }
+
+    pub fn clear_pending_load(&mut self) {
+        self.pending_load_slot = None;
+    }
+
+    pub fn encode_slot(&self, slot: SaveSlot) -> String {
+        match self.slots.get(slot) {
+            Some(state) => save_file::encode(state.checkpoint.as_ref(), &state.profile),
+            None => save_file::encode(None, &ProgressProfile::default()),
+        }
+    }
+
+    /// Encodes `slot` stamped as last played at `unix_time` without touching
+    /// the slot itself, so a write that fails leaves its summary alone.
+    pub fn encode_slot_played_at(&self, slot: SaveSlot, unix_time: i64) -> String {
+        let state = self.slots.get(slot);
+        let mut profile = state.map(|state| state.profile.clone()).unwrap_or_default();
+        profile.set_last_played(unix_time);
+        save_file::encode(state.and_then(|state| state.checkpoint.as_ref()), &profile)
+    }
+
+    pub fn restore_slot(&mut self, slot: SaveSlot, contents: SaveFileContents) {
+        let state = self.ensure_slot(slot);
+        state.checkpoint = contents.checkpoint;
+        state.profile = contents.profile;
+        if self.active_slot == slot {
+            self.discard_pending();
+            self.events.push(ProgressEvent::SlotLoaded(slot));
+        }
+    }
+
+    /// Marks `key` directly or as pending, depending on the kind's policy.
+    /// Returns false if it was already marked either way.
+    pub fn mark_entity_key(&mut self, kind: PersistentEntityKind, key: PersistentKey) -> bool {
+        if self.has_entity_key(kind, &key) {
+            return false;
+        }
+        let playtime_seconds = self.playtime_seconds();
+        let marked = (kind, key.clone());
+        match self.commit_policy(kind) {
+            CommitPolicy::Immediate => {
+                self.apply_marks(HashMap::from([(marked, playtime_seconds)]));
+            }
+            CommitPolicy::Transactional => {
+                self.pending_marks.insert(marked, playtime_seconds);
+            }
+        }
+
+        self.events.push(ProgressEvent::EntityMarked {
+            kind,
+            key: key.clone(),
+        });
+        if kind == PersistentEntityKind::Star {
+            let star_count = self.star_count();
+            self.events
+                .push(ProgressEvent::StarCollected { key, star_count });
+        }
+        true
+    }
+
+    /// Includes pending marks so collected entities stay gone until a reload.
+    pub fn has_entity_key(&self, kind: PersistentEntityKind, key: &PersistentKey) -> bool {
+        self.active_profile()
+            .is_some_and(|profile| profile.has_entity_key(kind, key))
+            || self.pending_marks.contains_key(&(kind, key.clone()))
+    }
+
+    pub fn entity_state(&self, key: &PersistentKey, field: &str) -> Option<EntityStateValue> {
+        self.active_profile()?.entity_state(key, field).cloned()
+    }
+
+    pub fn set_entity_state(
+        &mut self,
+        key: PersistentKey,
+        field: &str,
+        value: EntityStateValue,
+    ) -> bool {
+        self.active_profile_mut()
+            .set_entity_state(key, field, value)
+    }
+
+    pub fn clear_entity_state(&mut self, key: &PersistentKey, field: &str) -> bool {
+        self.active_profile_mut().clear_entity_state(key, field)
+    }
+
+    /// Unlocks go straight to the active slot's profile; no reload or new
+    /// room takes them back.
+    pub fn unlock_ability(&mut self, ability: Ability) -> bool {
+        let unlocked = self.active_profile_mut().unlock_ability(ability);
+        if unlocked {
+            self.events.push(ProgressEvent::AbilityUnlocked(ability));
+        }
+        unlocked
+    }
+
+    pub fn abilities(&self) -> AbilitySet {
+        self.active_profile()
+            .map(ProgressProfile::abilities)
+            .unwrap_or_default()
+    }
+
+    pub fn mark_room_explored(&mut self, room: RoomId) -> bool {
+        let explored = self.active_profile_mut().mark_room_explored(room);
+        if explored {
+            self.events.push(ProgressEvent::RoomExplored(room));
+        }
+        explored
+    }
+
+    pub fn is_room_explored(&self, room: RoomId) -> bool {
+        self.active_profile()
+            .is_some_and(|profile| profile.is_room_explored(room))
+    }
+
+    pub fn list_explored_rooms(&self) -> Vec<RoomId> {
+        self.active_profile()
+            .map(ProgressProfile::list_explored_rooms)
+            .unwrap_or_default()
+    }
+
+    /// Counts pending stars too, matching what the player has picked up.
+    pub fn star_count(&self) -> usize {
+        let pending = self
+            .pending_marks
+            .keys()
+            .filter(|(kind, _)| *kind == PersistentEntityKind::Star)
+            .count();
+        self.active_profile().map_or(0, ProgressProfile::star_count) + pending
+    }
+}
+
+thread_local! {
+    static GLOBAL: ProgressContext = ProgressContext::default();
+}
+
+/// The context behind the free functions below, shared by the Godot nodes.
+pub fn global() -> ProgressContext {
+    GLOBAL.with(ProgressContext::clone)
+}
+
+fn with_repository<T>(f: impl FnOnce(&ProgressRepository) -> T) -> T {
+    GLOBAL.with(|context| context.read(f))
+}
+
+fn with_repository_mut<T>(f: impl FnOnce(&mut ProgressRepository) -> T) -> T {
+    GLOBAL.with(|context| context.write(f))
+}
+
+pub fn make_legacy_key(room: RoomId, position: Vector2) -> PersistentKey {
+    PersistentKey::Legacy {
+        room,
+        position: (position.x as i32, position.y as i32),
+    }
+}
+
+pub fn active_slot() -> SaveSlot {
+    with_repository(ProgressRepository::active_slot)
+}
+
+pub fn start_new_game(slot: SaveSlot) {
+    with_repository_mut(|repository| repository.start_new_game(slot));
+}
+
+pub fn restart_run(slot: SaveSlot) {
+    with_repository_mut(|repository| repository.restart_run(slot));
+}
+
+pub fn copy_slot(from: SaveSlot, to: SaveSlot) -> bool {
+    with_repository_mut(|repository| repository.copy_slot(from, to))
+}
+
+pub fn delete_slot(slot: SaveSlot) {
+    with_repository_mut(|repository| repository.delete_slot(slot));
+}
+
+pub fn slot_summary(slot: SaveSlot) -> SlotSummary {
+    with_repository(|repository| repository.slot_summary(slot))
+}
+
+pub fn most_recent_slot() -> Option<SaveSlot> {
+    with_repository(ProgressRepository::most_recent_slot)
+}
+
+pub fn add_playtime(seconds: f64) {
+    with_repository_mut(|repository| repository.add_playtime(seconds));
+}
+
+pub fn add_room_time(room: RoomId, seconds: f64) {
+    with_repository_mut(|repository| repository.add_room_time(room, seconds));
+}
+
+pub fn record_death(room: RoomId, cause: DeathCause) {
+    with_repository_mut(|repository| repository.record_death(room, cause));
+}
+
+pub fn record_jump() {
+    with_repository_mut(ProgressRepository::record_jump);
+}
+
+pub fn record_room_transition() {
+    with_repository_mut(ProgressRepository::record_room_transition);
+}
+
+pub fn statistics() -> PlayStatistics {
+    with_repository(ProgressRepository::statistics)
+}
+
+pub fn playtime_seconds() -> f64 {
+    with_repository(ProgressRepository::playtime_seconds)
+}
+
+pub fn touch_slot(slot: SaveSlot, unix_time: i64) {
+    with_repository_mut(|repository| repository.touch_slot(slot, unix_time));
+}
+
+pub fn has_save(slot: SaveSlot) -> bool {
+    with_repository(|repository| repository.has_save(slot))
+}
+
+pub fn queue_load(slot: SaveSlot) -> bool {
+    with_repository_mut(|repository| repository.queue_load(slot))
+}
+
+pub fn clear_pending_load() {
+    with_repository_mut(ProgressRepository::clear_pending_load);
+}
+
+pub fn encode_slot_played_at(slot: SaveSlot, unix_time: i64) -> String {
+    with_repository(|repository| repository.encode_slot_played_at(slot, unix_time))
+}
+
+pub fn export_slot(slot: SaveSlot) -> String {
+    with_repository(|repository| repository.export_slot(slot))
+}
+
+pub fn import_slot(
+    slot: SaveSlot,
+    text: &str,
+    room_exists: impl FnMut(RoomId) -> bool,
+) -> Result<(), ImportError> {
+    with_repository_mut(|repository| repository.import_slot(slot, text, room_exists))
+}
+
+/// Decodes `text` into `slot`. With a table, legacy keys are rewritten to
+/// LDtk IIDs and the report lists the ones the table doesn't know.
+pub fn restore_slot(
+    slot: SaveSlot,
+    text: &str,
+    legacy_iids: Option<&LegacyIidTable>,
+) -> Result<LegacyKeyReport, SaveFileError> {
+    let context = MigrationContext {
+        legacy_iids: legacy_iids.map(|table| table as _),
+    };
+    let mut contents = save_file::decode(text, &context)?;
+    let report = legacy_iids
+        .map(|table| table.resolve_slot(contents.checkpoint.as_mut(), &mut contents.profile))
+        .unwrap_or_default();
+    with_repository_mut(|repository| repository.restore_slot(slot, contents));
+    Ok(report)
+}
+
+pub fn commit_policy(kind: PersistentEntityKind) -> CommitPolicy {
+    with_repository(|repository| repository.commit_policy(kind))
+}
+
+pub fn set_commit_policy(kind: PersistentEntityKind, policy: CommitPolicy) {
+    with_repository_mut(|repository| repository.set_commit_policy(kind, policy));
+}
+
+pub fn unlocked_abilities() -> AbilitySet {
+    with_repository(ProgressRepository::abilities)
+}
+
+pub fn is_room_explored(room: RoomId) -> bool {
+    with_repository(|repository| repository.is_room_explored(room))
+}
+
+pub fn list_explored_rooms() -> Vec<RoomId> {
+    with_repository(ProgressRepository::list_explored_rooms)
+}
+
+pub fn get_star_count() -> usize {
+    with_repository(ProgressRepository::star_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::core::world::RoomId;
+
+    fn room(x: i32, y: i32) -> RoomId {
+        RoomId::new(x, y)
+    }
+
+    #[test]
+    fn checkpoint_roundtrip_and_pending_load() {
+        let mut progress = ProgressRepository::default();
+
+        let room = room(1, 2);
+        let position = Vector2::new(10.0, 20.0);
+        let saved = progress.save_checkpoint(DEFAULT_SAVE_SLOT, room, position, None);
+
+        assert_eq!(saved.room, room);
+        assert_eq!(saved.position, position);
+        assert!(progress.has_save(DEFAULT_SAVE_SLOT));
+        assert!(progress.queue_load(DEFAULT_SAVE_SLOT));
+
+        let pending = progress.take_pending_load().expect("expected pending load");
+        assert_eq!(pending.room, room);
+        assert_eq!(pending.position, position);
+        assert!(progress.peek_checkpoint(DEFAULT_SAVE_SLOT).is_some());
+    }
+
+    #[test]
+    fn tracks_persistent_entities_and_stars() {
+        let mut progress = ProgressRepository::default();
+
+        let room = room(3, 4);
+        let key_pos = Vector2::new(5.0, 6.0);
+        let star_key = PersistentKey::Explicit("star:room_3_4".to_string());
+
+        assert!(
+            progress.mark_entity_key(PersistentEntityKind::Key, make_legacy_key(room, key_pos))
+        );
+        assert!(progress.mark_entity_key(PersistentEntityKind::Star, star_key.clone()));
+        assert!(
+            progress.has_entity_key(PersistentEntityKind::Key, &make_legacy_key(room, key_pos))
+        );
+        assert!(progress.has_entity_key(PersistentEntityKind::Star, &star_key));
+        assert_eq!(progress.star_count(), 1);
+    }
+
+    #[test]
+    fn checkpoint_key_prefers_explicit_id_over_position() {
+        let mut progress = ProgressRepository::default();
+
+        let checkpoint_key = PersistentKey::Explicit("checkpoint:alpha".to_string());
+        let snapshot = progress.save_checkpoint(
+            DEFAULT_SAVE_SLOT,
+            room(0, 1),
+            Vector2::new(16.0, 24.0),
+            Some(checkpoint_key.clone()),
+        );
+
+        assert!(snapshot.matches_checkpoint(
+            room(0, 1),
+            Vector2::new(32.0, 48.0),
+            0.1,
+            Some(&checkpoint_key),
+        ));
+        assert!(!snapshot.matches_checkpoint(
+            room(0, 1),
+            Vector2::new(16.0, 24.0),
+            0.1,
+            Some(&PersistentKey::Explicit("checkpoint:beta".to_string())),
+        ));
+    }
+
+    #[test]
+    fn tracks_explored_rooms_until_a_new_game() {
+        let mut progress = ProgressRepository::default();
+
+        assert!(progress.mark_room_explored(room(2, 3)));
+        assert!(!progress.mark_room_explored(room(2, 3)));
+        assert!(progress.is_room_explored(room(2, 3)));
+        assert_eq!(progress.list_explored_rooms(), vec![room(2, 3)]);
+
+        progress.start_new_game(DEFAULT_SAVE_SLOT);
+
+        assert!(!progress.has_save(DEFAULT_SAVE_SLOT));
+        assert!(!progress.is_room_explored(room(2, 3)));
+    }
+
+    #[test]
+    fn each_slot_owns_its_profile() {
+        let mut progress = ProgressRepository::default();
+
+        progress.save_checkpoint(0, room(0, 1), Vector2::new(8.0, 8.0), None);
+        progress.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star:a".to_string()),
+        );
+        progress.mark_room_explored(room(0, 1));
+
+        progress.save_checkpoint(1, room(1, 1), Vector2::new(8.0, 8.0), None);
+        assert_eq!(progress.active_slot(), 1);
+        assert_eq!(progress.star_count(), 0);
+        assert!(!progress.is_room_explored(room(0, 1)));
+        progress.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star:b".to_string()),
+        );
+        progress.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star:c".to_string()),
+        );
+        progress.save_checkpoint(1, room(1, 1), Vector2::new(8.0, 8.0), None);
+
+        assert!(progress.queue_load(0));
+        assert_eq!(progress.active_slot(), 0);
+        assert_eq!(progress.star_count(), 1);
+        assert_eq!(progress.list_explored_rooms(), vec![room(0, 1)]);
+
+        assert!(progress.queue_load(1));
+        assert_eq!(progress.star_count(), 2);
+    }
+
+    #[test]
+    fn queue_load_without_save_keeps_active_slot() {
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(1, room(1, 1), Vector2::ZERO, None);
+
+        assert!(!progress.queue_load(2));
+        assert_eq!(progress.active_slot(), 1);
+    }
+
+    #[test]
+    fn start_new_game_clears_only_that_slot() {
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        progress.mark_room_explored(room(0, 1));
+        progress.save_checkpoint(2, room(2, 1), Vector2::ZERO, None);
+        progress.mark_room_explored(room(2, 1));
+
+        progress.start_new_game(2);
+
+        assert_eq!(progress.active_slot(), 2);
+        assert!(!progress.has_save(2));
+        assert!(progress.list_explored_rooms().is_empty());
+        assert!(progress.has_save(0));
+        assert!(progress.queue_load(0));
+        assert!(progress.is_room_explored(room(0, 1)));
+    }
+
+    #[test]
+    fn restore_slot_replaces_checkpoint_and_profile() {
+        let mut saved = ProgressRepository::default();
+        saved.save_checkpoint(DEFAULT_SAVE_SLOT, room(4, 4), Vector2::new(1.0, 1.0), None);
+        saved.mark_room_explored(room(4, 4));
+        let encoded = saved.encode_slot(DEFAULT_SAVE_SLOT);
+
+        let mut progress = ProgressRepository::default();
+        progress.mark_room_explored(room(9, 9));
+        let contents = save_file::decode(&encoded, &MigrationContext::default()).expect("decodes");
+        progress.restore_slot(DEFAULT_SAVE_SLOT, contents);
+
+        assert!(progress.has_save(DEFAULT_SAVE_SLOT));
+        assert!(progress.is_room_explored(room(4, 4)));
+        assert!(!progress.is_room_explored(room(9, 9)));
+    }
+
+    #[test]
+    fn slot_summary_reports_checkpoint_and_profile() {
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(1, room(2, 1), Vector2::ZERO, None);
+        progress.mark_room_explored(room(1, 1));
+        progress.mark_room_explored(room(2, 1));
+        progress.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star:a".to_string()),
+        );
+        progress.add_playtime(90.5);
+        progress.add_playtime(-3.0);
+        progress.save_checkpoint(1, room(2, 1), Vector2::ZERO, None);
+        progress.touch_slot(1, 1_700_000_000);
+
+        assert_eq!(
+            progress.slot_summary(1),
+            SlotSummary {
+                slot: 1,
+                room: Some(room(2, 1)),
+                star_count: 1,
+                explored_room_count: 2,
+                last_played_unix: Some(1_700_000_000),
+                playtime_seconds: 90.5,
+            }
+        );
+        assert!(!progress.slot_summary(2).has_save());
+    }
+
+    #[test]
+    fn copy_and_delete_slots() {
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        progress.mark_room_explored(room(0, 1));
+
+        assert!(!progress.copy_slot(1, 2));
+        assert!(!progress.copy_slot(0, 0));
+        assert!(progress.copy_slot(0, 2));
+        assert_eq!(progress.slot_summary(2).room, Some(room(0, 1)));
+        assert_eq!(progress.slot_summary(2).explored_room_count, 1);
+
+        progress.delete_slot(0);
+        assert!(!progress.has_save(0));
+        assert_eq!(progress.slot_summary(0).explored_room_count, 0);
+        assert!(progress.has_save(2));
+    }
+
+    #[test]
+    fn most_recent_slot_prefers_latest_save() {
+        let mut progress = ProgressRepository::default();
+        assert_eq!(progress.most_recent_slot(), None);
+
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        progress.save_checkpoint(2, room(0, 1), Vector2::ZERO, None);
+        assert_eq!(progress.most_recent_slot(), Some(0));
+
+        progress.touch_slot(0, 100);
+        progress.touch_slot(2, 200);
+        progress.touch_slot(1, 300);
+        assert_eq!(progress.most_recent_slot(), Some(2));
+    }
+
+    #[test]
+    fn encoding_with_a_play_time_leaves_the_slot_untouched() {
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+
+        let encoded = progress.encode_slot_played_at(0, 1_700_000_000);
+
+        assert!(encoded.contains("last_played 1700000000"));
+        assert_eq!(progress.slot_summary(0).last_played_unix, None);
+    }
+
+    #[test]
+    fn entity_state_is_per_slot_and_cleared_by_new_game() {
+        let mut progress = ProgressRepository::default();
+        let door = PersistentKey::Explicit("door".to_string());
+
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        assert!(progress.set_entity_state(door.clone(), "open", EntityStateValue::Bool(true)));
+        assert!(!progress.set_entity_state(door.clone(), "two words", EntityStateValue::Int(1)));
+        assert_eq!(
+            progress.entity_state(&door, "open"),
+            Some(EntityStateValue::Bool(true))
+        );
+
+        progress.save_checkpoint(1, room(0, 1), Vector2::ZERO, None);
+        assert_eq!(progress.entity_state(&door, "open"), None);
+
+        assert!(progress.queue_load(0));
+        assert!(progress.clear_entity_state(&door, "open"));
+        assert!(!progress.clear_entity_state(&door, "open"));
+
+        progress.set_entity_state(door.clone(), "open", EntityStateValue::Bool(true));
+        progress.start_new_game(0);
+        assert_eq!(progress.entity_state(&door, "open"), None);
+    }
+
+    #[test]
+    fn transactional_marks_commit_at_checkpoint_and_drop_on_reload() {
+        let mut progress = ProgressRepository::default();
+        let star = PersistentKey::Explicit("star:a".to_string());
+        let key = PersistentKey::Explicit("key:a".to_string());
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+
+        assert!(progress.mark_entity_key(PersistentEntityKind::Star, star.clone()));
+        assert!(!progress.mark_entity_key(PersistentEntityKind::Star, star.clone()));
+        assert!(progress.has_entity_key(PersistentEntityKind::Star, &star));
+        assert_eq!(progress.star_count(), 1);
+        assert_eq!(progress.slot_summary(0).star_count, 0);
+        assert!(!progress.encode_slot(0).contains("star:a"));
+
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        assert_eq!(progress.slot_summary(0).star_count, 1);
+
+        assert!(progress.mark_entity_key(PersistentEntityKind::Key, key.clone()));
+        assert!(progress.queue_load(0));
+        assert!(!progress.has_entity_key(PersistentEntityKind::Key, &key));
+        assert!(progress.has_entity_key(PersistentEntityKind::Star, &star));
+    }
+
+    #[test]
+    fn immediate_kinds_skip_the_pending_layer() {
+        let mut progress = ProgressRepository::default();
+        let lock = PersistentKey::Explicit("lock:a".to_string());
+        let star = PersistentKey::Explicit("star:a".to_string());
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+
+        assert_eq!(
+            progress.commit_policy(PersistentEntityKind::Lock),
+            CommitPolicy::Transactional
+        );
+        progress.mark_entity_key(PersistentEntityKind::Star, star.clone());
+        progress.set_commit_policy(PersistentEntityKind::Lock, CommitPolicy::Immediate);
+        progress.mark_entity_key(PersistentEntityKind::Lock, lock.clone());
+        assert!(progress.queue_load(0));
+
+        assert!(progress.has_entity_key(PersistentEntityKind::Lock, &lock));
+        assert!(!progress.has_entity_key(PersistentEntityKind::Star, &star));
+
+        progress.mark_entity_key(PersistentEntityKind::Star, star.clone());
+        progress.set_commit_policy(PersistentEntityKind::Star, CommitPolicy::Immediate);
+        assert_eq!(progress.slot_summary(0).star_count, 1);
+    }
+
+    #[test]
+    fn statistics_survive_reloads_and_time_committed_stars() {
+        let mut progress = ProgressRepository::default();
+        let star = PersistentKey::Explicit("star:a".to_string());
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+        progress.add_playtime(30.0);
+
+        progress.mark_entity_key(PersistentEntityKind::Star, star.clone());
+        progress.record_death(room(0, 1), DeathCause::Hazard);
+        assert!(progress.queue_load(0));
+
+        assert_eq!(progress.statistics().death_count(), 1);
+        assert_eq!(progress.statistics().star_collect_time(&star), None);
+
+        progress.add_playtime(10.0);
+        progress.mark_entity_key(PersistentEntityKind::Star, star.clone());
+        progress.record_jump();
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+
+        assert_eq!(progress.statistics().star_collect_time(&star), Some(40.0));
+        assert_eq!(progress.statistics().jumps(), 1);
+        assert_eq!(progress.playtime_seconds(), 40.0);
+    }
+
+    #[test]
+    fn restarting_before_the_first_checkpoint_keeps_statistics() {
+        let mut progress = ProgressRepository::default();
+        progress.mark_room_explored(room(0, 1));
+        progress.add_playtime(12.0);
+        progress.record_jump();
+        progress.record_death(room(0, 1), DeathCause::Hazard);
+
+        progress.restart_run(0);
+
+        assert!(progress.list_explored_rooms().is_empty());
+        assert_eq!(progress.statistics().death_count(), 1);
+        assert_eq!(progress.statistics().jumps(), 1);
+        assert_eq!(progress.playtime_seconds(), 12.0);
+
+        progress.start_new_game(0);
+
+        assert_eq!(progress.statistics().death_count(), 0);
+        assert_eq!(progress.playtime_seconds(), 0.0);
+    }
+
+    #[test]
+    fn ability_unlocks_survive_reloads_until_a_new_game() {
+        let mut progress = ProgressRepository::default();
+        let pickup = PersistentKey::Explicit("pickup:dash".to_string());
+        progress.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+
+        assert!(progress.mark_entity_key(PersistentEntityKind::AbilityPickup, pickup.clone()));
+        assert!(progress.unlock_ability(Ability::Dash));
+        assert!(!progress.unlock_ability(Ability::Dash));
+        assert!(progress.queue_load(0));
+
+        assert!(progress.abilities().contains(Ability::Dash));
+        assert!(progress.has_entity_key(PersistentEntityKind::AbilityPickup, &pickup));
+        assert!(progress.encode_slot(0).contains("ability dash"));
+
+        progress.start_new_game(0);
+        assert_eq!(progress.abilities(), AbilitySet::default());
+    }
+
+    fn record_events(progress: &ProgressContext) -> Rc<RefCell<Vec<ProgressEvent>>> {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+        progress.subscribe(move |event| sink.borrow_mut().push(event.clone()));
+        received
+    }
+
+    #[test]
+    fn notifies_subscribers_of_changes() {
+        let progress = ProgressContext::default();
+        let star = PersistentKey::Explicit("star:a".to_string());
+        let received = record_events(&progress);
+
+        let snapshot = progress.write(|repository| {
+            let snapshot = repository.save_checkpoint(0, room(0, 1), Vector2::ZERO, None);
+            repository.mark_room_explored(room(0, 1));
+            repository.mark_room_explored(room(0, 1));
+            snapshot
+        });
+        progress.write(|repository| {
+            repository.mark_entity_key(PersistentEntityKind::Star, star.clone())
+        });
+        assert!(progress.write(|repository| repository.queue_load(0)));
+
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ProgressEvent::CheckpointSaved { slot: 0, snapshot },
+                ProgressEvent::RoomExplored(room(0, 1)),
+                ProgressEvent::EntityMarked {
+                    kind: PersistentEntityKind::Star,
+                    key: star.clone(),
+                },
+                ProgressEvent::StarCollected {
+                    key: star,
+                    star_count: 1,
+                },
+                ProgressEvent::SlotLoaded(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn notifies_subscribers_of_slot_copies_and_deletes() {
+        let progress = ProgressContext::default();
+        progress.write(|repository| repository.save_checkpoint(0, room(0, 1), Vector2::ZERO, None));
+        let received = record_events(&progress);
+
+        assert!(progress.write(|repository| repository.copy_slot(0, 1)));
+        assert!(!progress.write(|repository| repository.copy_slot(2, 1)));
+        progress.write(|repository| repository.delete_slot(0));
+
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ProgressEvent::SlotCopied { from: 0, to: 1 },
+                ProgressEvent::SlotDeleted(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn subscribers_can_change_progress_and_unsubscribe() {
+        let progress = ProgressContext::default();
+        let chained = progress.subscribe({
+            let progress = progress.clone();
+            move |event| {
+                if *event == ProgressEvent::RoomExplored(room(0, 1)) {
+                    progress.write(|repository| repository.mark_room_explored(room(1, 1)));
+                }
+            }
+        });
+        let received = record_events(&progress);
+
+        progress.write(|repository| repository.mark_room_explored(room(0, 1)));
+        assert!(progress.unsubscribe(chained));
+        assert!(!progress.unsubscribe(chained));
+        progress.write(|repository| repository.mark_room_explored(room(2, 1)));
+
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                ProgressEvent::RoomExplored(room(0, 1)),
+                ProgressEvent::RoomExplored(room(1, 1)),
+                ProgressEvent::RoomExplored(room(2, 1)),
+            ]
+        );
+        assert!(progress.read(|repository| repository.is_room_explored(room(1, 1))));
+    }
+
+    #[test]
+    fn contexts_do_not_share_progress() {
+        let first = ProgressContext::default();
+        let second = ProgressContext::default();
+        let received = record_events(&second);
+
+        first.write(|repository| repository.mark_room_explored(room(0, 1)));
+
+        assert!(!second.read(|repository| repository.is_room_explored(room(0, 1))));
+        assert!(received.borrow().is_empty());
+        assert_eq!(first.clone(), first);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,622 @@
+//! Line-based text encoding for one save slot.
+//!
+//! Each record sits on its own line and starts with a tag, so QA can read a
+//! save file directly and diffs stay small between checkpoints.
+
+use std::fmt;
+
+use godot::prelude::*;
+
+use super::abilities::Ability;
+use super::entity_state::EntityStateValue;
+use super::migration::{self, LATEST_VERSION, MigrationContext, SaveDocument, SaveRecord};
+use super::statistics::DeathCause;
+use super::{PersistentEntityKind, PersistentKey, ProgressProfile, SaveSnapshot};
+use crate::core::world::RoomId;
+
+const HEADER_TAG: &str = "p1proto-save";
+const CHECKPOINT_TAG: &str = "checkpoint";
+const EXPLORED_TAG: &str = "explored";
+const ABILITY_TAG: &str = "ability";
+const PLAYTIME_TAG: &str = "playtime";
+const LAST_PLAYED_TAG: &str = "last_played";
+const STATE_TAG: &str = "state";
+const JUMPS_TAG: &str = "jumps";
+const TRANSITIONS_TAG: &str = "transitions";
+const DEATHS_TAG: &str = "deaths";
+const ROOM_TIME_TAG: &str = "room_time";
+const STAR_TIME_TAG: &str = "star_time";
+pub(super) const EXPLICIT_KEY_TAG: &str = "iid";
+pub(super) const LEGACY_KEY_TAG: &str = "legacy";
+const COMMENT_PREFIX: char = '#';
+
+#[derive(Default)]
+pub struct SaveFileContents {
+    pub checkpoint: Option<SaveSnapshot>,
+    pub profile: ProgressProfile,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SaveFileError {
+    MissingHeader,
+    UnsupportedVersion(u32),
+    Malformed { line: usize, content: String },
+}
+
+impl fmt::Display for SaveFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "save file does not start with a {HEADER_TAG} header"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "save file version {version} is not supported")
+            }
+            Self::Malformed { line, content } => {
+                write!(f, "malformed save record on line {line}: {content}")
+            }
+        }
+    }
+}
+
+pub fn encode(checkpoint: Option<&SaveSnapshot>, profile: &ProgressProfile) -> String {
+    let mut out = format!("{HEADER_TAG} {LATEST_VERSION}\n");
+
+    if let Some(snapshot) = checkpoint {
+        let mut line = format!(
+            "{CHECKPOINT_TAG} {} {} {} {}",
+            snapshot.room.x, snapshot.room.y, snapshot.position.x, snapshot.position.y
+        );
+        if let Some(key) = &snapshot.checkpoint_key {
+            line.push(' ');
+            line.push_str(&encode_key(key));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    if profile.playtime_seconds() > 0.0 {
+        out.push_str(&format!("{PLAYTIME_TAG} {}\n", profile.playtime_seconds()));
+    }
+    if let Some(unix_time) = profile.last_played_unix() {
+        out.push_str(&format!("{LAST_PLAYED_TAG} {unix_time}\n"));
+    }
+
+    let mut rooms = profile.list_explored_rooms();
+    rooms.sort_by_key(|room| (room.y, room.x));
+    for room in rooms {
+        out.push_str(&format!("{EXPLORED_TAG} {} {}\n", room.x, room.y));
+    }
+    for ability in profile.abilities().iter() {
+        out.push_str(&format!("{ABILITY_TAG} {}\n", ability.name()));
+    }
+
+    for kind in PersistentEntityKind::ALL {
+        // Hash-set order is random; sorting keeps consecutive saves diffable.
+        let mut lines: Vec<String> = profile
+            .entity_set(kind)
+            .iter()
+            .map(|key| format!("{} {}", kind.name(), encode_key(key)))
+            .collect();
+        lines.sort();
+        for line in lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    let mut state_lines: Vec<String> = profile
+        .entity_state
+        .iter()
+        .flat_map(|(key, fields)| {
+            fields.iter().map(move |(field, value)| {
+                format!(
+                    "{STATE_TAG} {field} {} {} {}",
+                    value.type_tag(),
+                    value.encode_tokens(),
+                    encode_key(key)
+                )
+            })
+        })
+        .collect();
+    state_lines.sort();
+    for line in state_lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    encode_statistics(&mut out, profile);
+    out
+}
+
+fn encode_statistics(out: &mut String, profile: &ProgressProfile) {
+    let statistics = profile.statistics();
+    if statistics.jumps() > 0 {
+        out.push_str(&format!("{JUMPS_TAG} {}\n", statistics.jumps()));
+    }
+    if statistics.room_transitions() > 0 {
+        out.push_str(&format!(
+            "{TRANSITIONS_TAG} {}\n",
+            statistics.room_transitions()
+        ));
+    }
+    for (room, cause, count) in statistics.deaths() {
+        out.push_str(&format!(
+            "{DEATHS_TAG} {} {} {} {count}\n",
+            room.x,
+            room.y,
+            cause.name()
+        ));
+    }
+    for (room, seconds) in statistics.room_times() {
+        out.push_str(&format!(
+            "{ROOM_TIME_TAG} {} {} {seconds}\n",
+            room.x, room.y
+        ));
+    }
+
+    let mut star_lines: Vec<String> = statistics
+        .star_collect_times()
+        .map(|(key, seconds)| format!("{STAR_TIME_TAG} {seconds} {}", encode_key(key)))
+        .collect();
+    star_lines.sort();
+    for line in star_lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+}
+
+/// Decodes a save written by this or any older build, upgrading it through
+/// the migration chain first.
+pub fn decode(text: &str, context: &MigrationContext) -> Result<SaveFileContents, SaveFileError> {
+    let mut document = parse_document(text)?;
+    migration::upgrade(&mut document, context)?;
+
+    let mut contents = SaveFileContents::default();
+    for record in document.records {
+        let malformed = || SaveFileError::Malformed {
+            line: record.line,
+            content: record.tokens.join(" "),
+        };
+        let tokens: Vec<&str> = record.tokens.iter().map(String::as_str).collect();
+
+        match tokens.as_slice() {
+            [CHECKPOINT_TAG, x, y, px, py, key @ ..] => {
+                let room = parse_room(x, y).ok_or_else(malformed)?;
+                let position = parse_position(px, py).ok_or_else(malformed)?;
+                contents.checkpoint = Some(if key.is_empty() {
+                    SaveSnapshot::new(room, position)
+                } else {
+                    let key = decode_key(key).ok_or_else(malformed)?;
+                    SaveSnapshot::with_checkpoint_key(room, position, key)
+                });
+            }
+            [PLAYTIME_TAG, seconds] => {
+                let seconds = parse_seconds(seconds).ok_or_else(malformed)?;
+                contents.profile.add_playtime(seconds);
+            }
+            [LAST_PLAYED_TAG, unix_time] => {
+                let unix_time = unix_time.parse().map_err(|_| malformed())?;
+                contents.profile.set_last_played(unix_time);
+            }
+            [EXPLORED_TAG, x, y] => {
+                let room = parse_room(x, y).ok_or_else(malformed)?;
+                contents.profile.mark_room_explored(room);
+            }
+            [ABILITY_TAG, name] => {
+                let ability = Ability::from_name(name).ok_or_else(malformed)?;
+                contents.profile.unlock_ability(ability);
+            }
+            [STATE_TAG, field, type_tag, rest @ ..] => {
+                let value_len = EntityStateValue::token_count(type_tag).ok_or_else(malformed)?;
+                if rest.len() < value_len {
+                    return Err(malformed());
+                }
+                let (value, key) = rest.split_at(value_len);
+                let value =
+                    EntityStateValue::decode_tokens(type_tag, value).ok_or_else(malformed)?;
+                let key = decode_key(key).ok_or_else(malformed)?;
+                if !contents.profile.set_entity_state(key, field, value) {
+                    return Err(malformed());
+                }
+            }
+            [JUMPS_TAG, count] => {
+                let count = count.parse().map_err(|_| malformed())?;
+                contents.profile.statistics_mut().set_jumps(count);
+            }
+            [TRANSITIONS_TAG, count] => {
+                let count = count.parse().map_err(|_| malformed())?;
+                contents
+                    .profile
+                    .statistics_mut()
+                    .set_room_transitions(count);
+            }
+            [DEATHS_TAG, x, y, cause, count] => {
+                let room = parse_room(x, y).ok_or_else(malformed)?;
+                let cause = DeathCause::from_name(cause).ok_or_else(malformed)?;
+                let count = count.parse().map_err(|_| malformed())?;
+                contents
+                    .profile
+                    .statistics_mut()
+                    .add_deaths(room, cause, count);
+            }
+            [ROOM_TIME_TAG, x, y, seconds] => {
+                let room = parse_room(x, y).ok_or_else(malformed)?;
+                let seconds = parse_seconds(seconds).ok_or_else(malformed)?;
+                contents
+                    .profile
+                    .statistics_mut()
+                    .add_room_time(room, seconds);
+            }
+            [STAR_TIME_TAG, seconds, key @ ..] => {
+                let seconds = parse_seconds(seconds).ok_or_else(malformed)?;
+                let key = decode_key(key).ok_or_else(malformed)?;
+                contents
+                    .profile
+                    .statistics_mut()
+                    .record_star_collected(key, seconds);
+            }
+            [tag, key @ ..] => {
+                let kind = PersistentEntityKind::from_name(tag).ok_or_else(malformed)?;
+                let key = decode_key(key).ok_or_else(malformed)?;
+                contents.profile.mark_entity_key(kind, key);
+            }
+            [] => return Err(malformed()),
+        }
+    }
+
+    Ok(contents)
+}
+
+/// Records whose tag is neither a known record nor an entity kind, as
+/// `(line, tag)` pairs, after upgrading the document to the latest version.
+pub(super) fn unknown_record_tags(
+    text: &str,
+    context: &MigrationContext,
+) -> Result<Vec<(usize, String)>, SaveFileError> {
+    const RECORD_TAGS: [&str; 11] = [
+        CHECKPOINT_TAG,
+        PLAYTIME_TAG,
+        LAST_PLAYED_TAG,
+        EXPLORED_TAG,
+        ABILITY_TAG,
+        STATE_TAG,
+        JUMPS_TAG,
+        TRANSITIONS_TAG,
+        DEATHS_TAG,
+        ROOM_TIME_TAG,
+        STAR_TIME_TAG,
+    ];
+
+    let mut document = parse_document(text)?;
+    migration::upgrade(&mut document, context)?;
+    Ok(document
+        .records
+        .into_iter()
+        .filter_map(|record| {
+            let tag = record.tokens.first()?;
+            let known = RECORD_TAGS.contains(&tag.as_str())
+                || PersistentEntityKind::from_name(tag).is_some();
+            (!known).then(|| (record.line, tag.clone()))
+        })
+        .collect())
+}
+
+fn parse_document(text: &str) -> Result<SaveDocument, SaveFileError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with(COMMENT_PREFIX));
+
+    let Some((header_line, header)) = lines.next() else {
+        return Err(SaveFileError::MissingHeader);
+    };
+
+    Ok(SaveDocument {
+        version: parse_header(header_line, header)?,
+        records: lines
+            .map(|(line, content)| SaveRecord {
+                line,
+                tokens: content.split_whitespace().map(str::to_string).collect(),
+            })
+            .collect(),
+    })
+}
+
+fn parse_header(line_number: usize, header: &str) -> Result<u32, SaveFileError> {
+    let mut tokens = header.split_whitespace();
+    if tokens.next() != Some(HEADER_TAG) {
+        return Err(SaveFileError::MissingHeader);
+    }
+
+    match (tokens.next().and_then(|v| v.parse().ok()), tokens.next()) {
+        (Some(version), None) => Ok(version),
+        _ => Err(SaveFileError::Malformed {
+            line: line_number,
+            content: header.to_string(),
+        }),
+    }
+}
+
+pub(super) fn encode_key(key: &PersistentKey) -> String {
+    match key {
+        PersistentKey::Explicit(iid) => format!("{EXPLICIT_KEY_TAG} {iid}"),
+        PersistentKey::Legacy { room, position } => format!(
+            "{LEGACY_KEY_TAG} {} {} {} {}",
+            room.x, room.y, position.0, position.1
+        ),
+    }
+}
+
+fn decode_key(tokens: &[&str]) -> Option<PersistentKey> {
+    match tokens {
+        [EXPLICIT_KEY_TAG, iid] => Some(PersistentKey::Explicit(iid.to_string())),
+        [LEGACY_KEY_TAG, x, y, px, py] => Some(PersistentKey::Legacy {
+            room: parse_room(x, y)?,
+            position: (px.parse().ok()?, py.parse().ok()?),
+        }),
+        _ => None,
+    }
+}
+
+fn parse_room(x: &str, y: &str) -> Option<RoomId> {
+    Some(RoomId::new(x.parse().ok()?, y.parse().ok()?))
+}
+
+fn parse_position(x: &str, y: &str) -> Option<Vector2> {
+    let position = Vector2::new(x.parse().ok()?, y.parse().ok()?);
+    (position.x.is_finite() && position.y.is_finite()).then_some(position)
+}
+
+/// A finite, non-negative duration.
+fn parse_seconds(seconds: &str) -> Option<f64> {
+    let seconds: f64 = seconds.parse().ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then_some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(x: i32, y: i32) -> RoomId {
+        RoomId::new(x, y)
+    }
+
+    #[test]
+    fn roundtrips_checkpoint_and_profile() {
+        let mut profile = ProgressProfile::default();
+        profile.mark_room_explored(room(0, 1));
+        profile.mark_room_explored(room(1, 1));
+        profile.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("b3ebd360-fa90-11f0-943d-11ec93e38c1d".to_string()),
+        );
+        profile.mark_entity_key(
+            PersistentEntityKind::Lock,
+            PersistentKey::Legacy {
+                room: room(0, 1),
+                position: (304, -216),
+            },
+        );
+        profile.add_playtime(125.25);
+        profile.set_last_played(1_760_000_000);
+        profile.set_entity_state(
+            PersistentKey::Explicit("crate".to_string()),
+            "position",
+            EntityStateValue::Vector(Vector2::new(132.5, 220.0)),
+        );
+        profile.set_entity_state(
+            PersistentKey::Legacy {
+                room: room(2, 2),
+                position: (48, 208),
+            },
+            "door",
+            EntityStateValue::Enum("open".to_string()),
+        );
+        let checkpoint = SaveSnapshot::with_checkpoint_key(
+            room(2, 1),
+            Vector2::new(12.5, 24.0),
+            PersistentKey::Explicit("checkpoint:alpha".to_string()),
+        );
+
+        let text = encode(Some(&checkpoint), &profile);
+        let decoded = decode(&text, &MigrationContext::default()).expect("decodes");
+
+        assert_eq!(decoded.checkpoint, Some(checkpoint));
+        assert_eq!(encode(decoded.checkpoint.as_ref(), &decoded.profile), text);
+        assert_eq!(decoded.profile.star_count(), 1);
+        assert_eq!(decoded.profile.playtime_seconds(), 125.25);
+        assert_eq!(decoded.profile.last_played_unix(), Some(1_760_000_000));
+        assert_eq!(
+            decoded
+                .profile
+                .entity_state(&PersistentKey::Explicit("crate".to_string()), "position"),
+            Some(&EntityStateValue::Vector(Vector2::new(132.5, 220.0)))
+        );
+        assert!(decoded.profile.has_entity_key(
+            PersistentEntityKind::Lock,
+            &PersistentKey::Legacy {
+                room: room(0, 1),
+                position: (304, -216),
+            },
+        ));
+    }
+
+    #[test]
+    fn encodes_one_sorted_record_per_line() {
+        let mut profile = ProgressProfile::default();
+        profile.mark_room_explored(room(1, 1));
+        profile.mark_room_explored(room(0, 1));
+        profile.mark_entity_key(
+            PersistentEntityKind::Key,
+            PersistentKey::Explicit("b".to_string()),
+        );
+        profile.mark_entity_key(
+            PersistentEntityKind::Key,
+            PersistentKey::Explicit("a".to_string()),
+        );
+
+        let text = encode(
+            Some(&SaveSnapshot::new(room(0, 1), Vector2::new(64.0, 64.0))),
+            &profile,
+        );
+
+        assert_eq!(
+            text,
+            "p1proto-save 6\n\
+             checkpoint 0 1 64 64\n\
+             explored 0 1\n\
+             explored 1 1\n\
+             key iid a\n\
+             key iid b\n"
+        );
+    }
+
+    #[test]
+    fn roundtrips_abilities_and_rejects_unknown_ones() {
+        let mut profile = ProgressProfile::default();
+        profile.unlock_ability(Ability::WallJump);
+        profile.unlock_ability(Ability::DoubleJump);
+        profile.mark_entity_key(
+            PersistentEntityKind::AbilityPickup,
+            PersistentKey::Explicit("pickup".to_string()),
+        );
+
+        let text = encode(None, &profile);
+        let decoded = decode(&text, &MigrationContext::default()).expect("decodes");
+
+        assert!(text.contains("ability double_jump\nability wall_jump\n"));
+        assert!(text.contains("ability_pickup iid pickup\n"));
+        assert_eq!(decoded.profile.abilities(), profile.abilities());
+        assert_eq!(
+            decode(
+                "p1proto-save 6\nability glide\n",
+                &MigrationContext::default()
+            )
+            .err(),
+            Some(SaveFileError::Malformed {
+                line: 2,
+                content: "ability glide".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let decoded = decode(
+            "# exported by QA\np1proto-save 1\n\nexplored 2 3\n",
+            &MigrationContext::default(),
+        )
+        .unwrap();
+
+        assert!(decoded.checkpoint.is_none());
+        assert!(decoded.profile.is_room_explored(room(2, 3)));
+    }
+
+    #[test]
+    fn reads_version_one_files_without_play_metadata() {
+        let decoded = decode(
+            "p1proto-save 1\ncheckpoint 0 1 8 8\nexplored 0 1\n",
+            &MigrationContext::default(),
+        )
+        .unwrap();
+
+        assert_eq!(decoded.profile.playtime_seconds(), 0.0);
+        assert_eq!(decoded.profile.last_played_unix(), None);
+        assert!(decoded.checkpoint.is_some());
+    }
+
+    #[test]
+    fn rejects_unknown_version_and_missing_header() {
+        assert_eq!(
+            decode("p1proto-save 99\n", &MigrationContext::default()).err(),
+            Some(SaveFileError::UnsupportedVersion(99))
+        );
+        assert_eq!(
+            decode("explored 0 1\n", &MigrationContext::default()).err(),
+            Some(SaveFileError::MissingHeader)
+        );
+        assert_eq!(
+            decode("", &MigrationContext::default()).err(),
+            Some(SaveFileError::MissingHeader)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_state_records() {
+        for record in [
+            "state open bool maybe iid door",
+            "state open color red iid door",
+            "state pos vec 1 iid door",
+            "state open bool true",
+        ] {
+            let text = format!("p1proto-save 5\n{record}\n");
+            assert!(
+                matches!(
+                    decode(&text, &MigrationContext::default()),
+                    Err(SaveFileError::Malformed { line: 2, .. })
+                ),
+                "{record}"
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrips_statistics() {
+        let mut profile = ProgressProfile::default();
+        let statistics = profile.statistics_mut();
+        statistics.record_jump();
+        statistics.record_jump();
+        statistics.record_room_transition();
+        statistics.record_death(room(1, 1), DeathCause::Hazard);
+        statistics.record_death(room(0, 1), DeathCause::Respawn);
+        statistics.add_room_time(room(1, 1), 42.5);
+        statistics.record_star_collected(PersistentKey::Explicit("star".to_string()), 61.25);
+
+        let text = encode(None, &profile);
+        let decoded = decode(&text, &MigrationContext::default()).expect("decodes");
+
+        assert!(text.ends_with(
+            "jumps 2\n\
+             transitions 1\n\
+             deaths 0 1 respawn 1\n\
+             deaths 1 1 hazard 1\n\
+             room_time 1 1 42.5\n\
+             star_time 61.25 iid star\n"
+        ));
+        assert_eq!(decoded.profile.statistics(), profile.statistics());
+    }
+
+    #[test]
+    fn rejects_malformed_statistics_records() {
+        for record in [
+            "jumps -1",
+            "deaths 0 1 drowned 1",
+            "room_time 0 1 inf",
+            "star_time 5",
+        ] {
+            let text = format!("p1proto-save 5\n{record}\n");
+            assert!(
+                matches!(
+                    decode(&text, &MigrationContext::default()),
+                    Err(SaveFileError::Malformed { line: 2, .. })
+                ),
+                "{record}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_malformed_record_line() {
+        assert_eq!(
+            decode(
+                "p1proto-save 1\nexplored 0 1\ngem iid x\n",
+                &MigrationContext::default()
+            )
+            .err(),
+            Some(SaveFileError::Malformed {
+                line: 3,
+                content: "gem iid x".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Play statistics kept per profile.
+//!
+//! Counters only ever grow during play; they are not rolled back with
+//! transactional marks, so a death still counts after the checkpoint reloads.
+
+use std::collections::HashMap;
+
+use super::PersistentKey;
+use crate::core::world::RoomId;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum DeathCause {
+    /// Touched a hazard tile or body.
+    Hazard,
+    /// Pressed the respawn action.
+    Respawn,
+}
+
+impl DeathCause {
+    pub const ALL: [Self; 2] = [Self::Hazard, Self::Respawn];
+
+    /// Stable name used in save files and by `SaveApi`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hazard => "hazard",
+            Self::Respawn => "respawn",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|cause| cause.name() == name)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayStatistics {
+    deaths: HashMap<(RoomId, DeathCause), u64>,
+    jumps: u64,
+    room_transitions: u64,
+    room_seconds: HashMap<RoomId, f64>,
+    /// Profile playtime at the moment each star was picked up.
+    star_collect_times: HashMap<PersistentKey, f64>,
+}
+
+impl PlayStatistics {
+    pub fn record_death(&mut self, room: RoomId, cause: DeathCause) {
+        self.add_deaths(room, cause, 1);
+    }
+
+    pub(super) fn add_deaths(&mut self, room: RoomId, cause: DeathCause, count: u64) {
+        *self.deaths.entry((room, cause)).or_default() += count;
+    }
+
+    pub fn death_count(&self) -> u64 {
+        self.deaths.values().sum()
+    }
+
+    pub fn deaths_in_room(&self, room: RoomId) -> u64 {
+        self.deaths
+            .iter()
+            .filter(|((death_room, _), _)| *death_room == room)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    pub fn deaths_by_cause(&self, cause: DeathCause) -> u64 {
+        self.deaths
+            .iter()
+            .filter(|((_, death_cause), _)| *death_cause == cause)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Every `(room, cause, count)` entry, sorted by room row, column, then cause.
+    pub fn deaths(&self) -> Vec<(RoomId, DeathCause, u64)> {
+        let mut deaths: Vec<_> = self
+            .deaths
+            .iter()
+            .map(|((room, cause), count)| (*room, *cause, *count))
+            .collect();
+        deaths.sort_by_key(|(room, cause, _)| (room.y, room.x, *cause));
+        deaths
+    }
+
+    pub fn record_jump(&mut self) {
+        self.jumps += 1;
+    }
+
+    pub(super) fn set_jumps(&mut self, jumps: u64) {
+        self.jumps = jumps;
+    }
+
+    pub fn jumps(&self) -> u64 {
+        self.jumps
+    }
+
+    pub fn record_room_transition(&mut self) {
+        self.room_transitions += 1;
+    }
+
+    pub(super) fn set_room_transitions(&mut self, room_transitions: u64) {
+        self.room_transitions = room_transitions;
+    }
+
+    pub fn room_transitions(&self) -> u64 {
+        self.room_transitions
+    }
+
+    /// Ignores non-finite and non-positive durations, like profile playtime.
+    pub fn add_room_time(&mut self, room: RoomId, seconds: f64) {
+        if seconds.is_finite() && seconds > 0.0 {
+            *self.room_seconds.entry(room).or_default() += seconds;
+        }
+    }
+
+    pub fn room_seconds(&self, room: RoomId) -> f64 {
+        self.room_seconds.get(&room).copied().unwrap_or_default()
+    }
+
+    /// Every room with recorded time, sorted by row then column.
+    pub fn room_times(&self) -> Vec<(RoomId, f64)> {
+        let mut times: Vec<_> = self
+            .room_seconds
+            .iter()
+            .map(|(room, seconds)| (*room, *seconds))
+            .collect();
+        times.sort_by_key(|(room, _)| (room.y, room.x));
+        times
+    }
+
+    /// A star collected again after a rollback keeps its latest time.
+    pub fn record_star_collected(&mut self, key: PersistentKey, playtime_seconds: f64) {
+        self.star_collect_times.insert(key, playtime_seconds);
+    }
+
+    #[cfg(test)]
+    pub fn star_collect_time(&self, key: &PersistentKey) -> Option<f64> {
+        self.star_collect_times.get(key).copied()
+    }
+
+    pub fn star_collect_times(&self) -> impl Iterator<Item = (&PersistentKey, f64)> {
+        self.star_collect_times
+            .iter()
+            .map(|(key, seconds)| (key, *seconds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(x: i32, y: i32) -> RoomId {
+        RoomId::new(x, y)
+    }
+
+    #[test]
+    fn aggregates_deaths_by_room_and_cause() {
+        let mut statistics = PlayStatistics::default();
+        statistics.record_death(room(1, 1), DeathCause::Hazard);
+        statistics.record_death(room(1, 1), DeathCause::Hazard);
+        statistics.record_death(room(0, 1), DeathCause::Respawn);
+        statistics.record_death(room(1, 1), DeathCause::Respawn);
+
+        assert_eq!(statistics.death_count(), 4);
+        assert_eq!(statistics.deaths_in_room(room(1, 1)), 3);
+        assert_eq!(statistics.deaths_by_cause(DeathCause::Respawn), 2);
+        assert_eq!(
+            statistics.deaths(),
+            vec![
+                (room(0, 1), DeathCause::Respawn, 1),
+                (room(1, 1), DeathCause::Hazard, 2),
+                (room(1, 1), DeathCause::Respawn, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn room_time_ignores_invalid_durations() {
+        let mut statistics = PlayStatistics::default();
+        statistics.add_room_time(room(2, 1), 1.5);
+        statistics.add_room_time(room(2, 1), 0.25);
+        statistics.add_room_time(room(2, 1), f64::NAN);
+        statistics.add_room_time(room(0, 1), -1.0);
+
+        assert_eq!(statistics.room_seconds(room(2, 1)), 1.75);
+        assert_eq!(statistics.room_times(), vec![(room(2, 1), 1.75)]);
+    }
+
+    #[test]
+    fn death_cause_names_roundtrip() {
+        for cause in DeathCause::ALL {
+            assert_eq!(DeathCause::from_name(cause.name()), Some(cause));
+        }
+        assert_eq!(DeathCause::from_name("drowned"), None);
+    }
+}
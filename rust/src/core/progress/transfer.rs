@@ -0,0 +1,203 @@
+//! Portable text form of one slot, so testers can paste their progress into
+//! a bug report and developers can load it.
+//!
+//! The text is the save file encoding without a checksum, so it can be read
+//! and edited by hand. Importing validates it against the running game first.
+
+use std::fmt;
+
+use super::migration::MigrationContext;
+use super::save_file::{self, SaveFileContents, SaveFileError};
+use super::{PersistentEntityKind, PersistentKey, ProgressRepository, SaveSlot};
+use crate::core::world::RoomId;
+
+#[derive(Debug, PartialEq)]
+pub enum ImportError {
+    Decode(SaveFileError),
+    /// `(line, tag)` for every record naming an entity kind this build lacks.
+    UnknownKinds(Vec<(usize, String)>),
+    /// Referenced rooms that have no room scene, sorted by row then column.
+    MissingRooms(Vec<RoomId>),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(error) => error.fmt(f),
+            Self::UnknownKinds(records) => {
+                let records: Vec<String> = records
+                    .iter()
+                    .map(|(line, tag)| format!("{tag} (line {line})"))
+                    .collect();
+                write!(f, "unknown entity kinds: {}", records.join(", "))
+            }
+            Self::MissingRooms(rooms) => {
+                let rooms: Vec<String> = rooms.iter().map(RoomId::to_string).collect();
+                write!(f, "rooms not found: {}", rooms.join(", "))
+            }
+        }
+    }
+}
+
+impl From<SaveFileError> for ImportError {
+    fn from(error: SaveFileError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+impl ProgressRepository {
+    pub fn export_slot(&self, slot: SaveSlot) -> String {
+        self.encode_slot(slot)
+    }
+
+    /// Validates `text` and replaces `slot` with it. Nothing changes when
+    /// validation fails.
+    pub fn import_slot(
+        &mut self,
+        slot: SaveSlot,
+        text: &str,
+        room_exists: impl FnMut(RoomId) -> bool,
+    ) -> Result<(), ImportError> {
+        let contents = validate(text, room_exists)?;
+        self.restore_slot(slot, contents);
+        Ok(())
+    }
+}
+
+/// Decodes `text` and checks that every entity kind and room it mentions
+/// exists in this build.
+pub fn validate(
+    text: &str,
+    mut room_exists: impl FnMut(RoomId) -> bool,
+) -> Result<SaveFileContents, ImportError> {
+    let context = MigrationContext::default();
+    let unknown = save_file::unknown_record_tags(text, &context)?;
+    if !unknown.is_empty() {
+        return Err(ImportError::UnknownKinds(unknown));
+    }
+
+    let contents = save_file::decode(text, &context)?;
+    let missing: Vec<RoomId> = referenced_rooms(&contents)
+        .into_iter()
+        .filter(|room| !room_exists(*room))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ImportError::MissingRooms(missing));
+    }
+    Ok(contents)
+}
+
+/// Every room named by the checkpoint, explored set, statistics or
+/// position-based keys, without duplicates.
+fn referenced_rooms(contents: &SaveFileContents) -> Vec<RoomId> {
+    let profile = &contents.profile;
+    let statistics = profile.statistics();
+
+    let mut keys: Vec<&PersistentKey> = PersistentEntityKind::ALL
+        .into_iter()
+        .flat_map(|kind| profile.entity_set(kind))
+        .chain(profile.entity_state.keys())
+        .chain(statistics.star_collect_times().map(|(key, _)| key))
+        .collect();
+    if let Some(key) = contents
+        .checkpoint
+        .as_ref()
+        .and_then(|snapshot| snapshot.checkpoint_key.as_ref())
+    {
+        keys.push(key);
+    }
+
+    let mut rooms: Vec<RoomId> = contents
+        .checkpoint
+        .iter()
+        .map(|snapshot| snapshot.room)
+        .chain(profile.explored_rooms.iter().copied())
+        .chain(statistics.deaths().into_iter().map(|(room, _, _)| room))
+        .chain(statistics.room_times().into_iter().map(|(room, _)| room))
+        .chain(keys.into_iter().filter_map(|key| match key {
+            PersistentKey::Legacy { room, .. } => Some(*room),
+            PersistentKey::Explicit(_) => None,
+        }))
+        .collect();
+    rooms.sort_by_key(|room| (room.y, room.x));
+    rooms.dedup();
+    rooms
+}
+
+#[cfg(test)]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+
+    fn room(x: i32, y: i32) -> RoomId {
+        RoomId::new(x, y)
+    }
+
+    fn known_rooms(room: RoomId) -> bool {
+        matches!((room.x, room.y), (0..=3, 1))
+    }
+
+    #[test]
+    fn exported_slot_imports_into_another_repository() {
+        let mut source = ProgressRepository::default();
+        source.mark_room_explored(room(1, 1));
+        source.mark_entity_key(
+            PersistentEntityKind::Star,
+            PersistentKey::Explicit("star".to_string()),
+        );
+        source.save_checkpoint(0, room(1, 1), Vector2::new(88.0, 120.0), None);
+        let text = source.export_slot(0);
+
+        let mut target = ProgressRepository::default();
+        assert_eq!(target.import_slot(0, &text, known_rooms), Ok(()));
+
+        assert_eq!(target.export_slot(0), text);
+        assert!(target.has_save(0));
+        assert_eq!(target.star_count(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_entity_kinds() {
+        let text = "p1proto-save 5\nexplored 1 1\ngem iid x\nstar iid y\ncoin iid z\n";
+
+        assert_eq!(
+            validate(text, known_rooms).err(),
+            Some(ImportError::UnknownKinds(vec![
+                (3, "gem".to_string()),
+                (5, "coin".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn rejects_rooms_without_a_scene() {
+        let text = "p1proto-save 5\n\
+                    checkpoint 9 9 64 64\n\
+                    explored 1 1\n\
+                    deaths 7 2 hazard 1\n\
+                    key legacy 5 1 40 56\n";
+
+        assert_eq!(
+            validate(text, known_rooms).err(),
+            Some(ImportError::MissingRooms(vec![
+                room(5, 1),
+                room(7, 2),
+                room(9, 9)
+            ]))
+        );
+    }
+
+    #[test]
+    fn failed_import_leaves_the_slot_untouched() {
+        let mut repository = ProgressRepository::default();
+        repository.mark_room_explored(room(2, 1));
+
+        assert!(
+            repository
+                .import_slot(0, "p1proto-save 5\nexplored 9 9\n", known_rooms)
+                .is_err()
+        );
+        assert!(repository.is_room_explored(room(2, 1)));
+    }
+}
@@ -1,5 +1,6 @@
 use godot::prelude::*;
 
+use super::progress::ProgressContext;
 use super::world::{BoundaryDetector, RoomId, SpawnPoint, SpawnResolver};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -21,14 +22,18 @@ pub enum DeathPlan {
     RestartGame,
 }
 
+/// Tracks the room the player is in and records rooms and deaths in the
+/// session's progress.
 pub struct RoomSession {
     current_room: RoomId,
+    progress: ProgressContext,
 }
 
 impl RoomSession {
-    pub fn new(initial_room: RoomId) -> Self {
+    pub fn new(initial_room: RoomId, progress: ProgressContext) -> Self {
         Self {
             current_room: initial_room,
+            progress,
         }
     }
 
@@ -37,7 +42,9 @@ impl RoomSession {
         resolver: &SpawnResolver,
         room_exists: impl FnMut(RoomId) -> bool,
     ) -> SpawnPoint {
-        let spawn = resolver.resolve(room_exists);
+        let spawn = self
+            .progress
+            .write(|repository| resolver.resolve(repository, room_exists));
         self.current_room = spawn.room;
         spawn
     }
@@ -82,11 +89,18 @@ impl RoomSession {
         })
     }
 
+    /// Makes `target_room` current and marks it explored.
     pub fn complete_transition(&mut self, target_room: RoomId) {
         self.current_room = target_room;
+        self.progress
+            .write(|repository| repository.mark_room_explored(target_room));
     }
 
-    pub fn plan_death(&self, has_checkpoint: bool) -> DeathPlan {
+    /// Reloads when the active slot has a checkpoint to reload.
+    pub fn plan_death(&self) -> DeathPlan {
+        let has_checkpoint = self
+            .progress
+            .read(|repository| repository.has_save(repository.active_slot()));
         if has_checkpoint {
             DeathPlan::ReloadCheckpoint
         } else {
@@ -98,7 +112,7 @@ impl RoomSession {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::progress;
+    use crate::core::progress::DEFAULT_SAVE_SLOT;
     use crate::core::world::{ROOM_WIDTH, RoomId, SpawnResolver};
 
     fn room(x: i32, y: i32) -> RoomId {
@@ -107,27 +121,34 @@ mod tests {
 
     #[test]
     fn resolves_start_from_pending_checkpoint() {
-        progress::reset_all();
-        progress::save_checkpoint(
-            progress::DEFAULT_SAVE_SLOT,
-            room(2, 1),
-            Vector2::new(12.0, 24.0),
-        );
-        assert!(progress::queue_load(progress::DEFAULT_SAVE_SLOT));
+        let progress = ProgressContext::default();
+        progress.write(|repository| {
+            repository.save_checkpoint(
+                DEFAULT_SAVE_SLOT,
+                room(2, 1),
+                Vector2::new(12.0, 24.0),
+                None,
+            );
+            assert!(repository.queue_load(DEFAULT_SAVE_SLOT));
+        });
 
         let resolver = SpawnResolver::new(room(0, 0), Vector2::new(1.0, 2.0));
-        let mut session = RoomSession::new(room(0, 0));
+        let mut session = RoomSession::new(room(0, 0), progress.clone());
         let spawn = session.resolve_start(&resolver, |_| true);
 
         assert_eq!(spawn.room, room(2, 1));
         assert_eq!(spawn.position, Vector2::new(12.0, 24.0));
         assert_eq!(session.current_room(), room(2, 1));
+        assert_eq!(
+            progress.write(|repository| repository.take_pending_load()),
+            None
+        );
     }
 
     #[test]
     fn plans_boundary_transition_when_room_exists() {
         let detector = BoundaryDetector::new(0.5);
-        let session = RoomSession::new(room(0, 1));
+        let session = RoomSession::new(room(0, 1), ProgressContext::default());
 
         let plan = session.plan_boundary_transition(
             &detector,
@@ -148,9 +169,16 @@ mod tests {
 
     #[test]
     fn death_plan_prefers_checkpoint_reload() {
-        let session = RoomSession::new(room(0, 0));
+        let progress = ProgressContext::default();
+        let mut session = RoomSession::new(room(0, 0), progress.clone());
+        assert_eq!(session.plan_death(), DeathPlan::RestartGame);
+
+        progress.write(|repository| {
+            repository.save_checkpoint(DEFAULT_SAVE_SLOT, room(0, 0), Vector2::ZERO, None)
+        });
+        assert_eq!(session.plan_death(), DeathPlan::ReloadCheckpoint);
 
-        assert_eq!(session.plan_death(true), DeathPlan::ReloadCheckpoint);
-        assert_eq!(session.plan_death(false), DeathPlan::RestartGame);
+        session.complete_transition(room(1, 0));
+        assert!(progress.read(|repository| repository.is_room_explored(room(1, 0))));
     }
 }
@@ -0,0 +1,385 @@
+//! Player preferences, kept apart from save slots.
+//!
+//! Settings use the same line-based text style as save files: a versioned
+//! header, then one `<name> <value...>` line per setting. Unknown names are
+//! skipped so older builds can read files written by newer ones.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+const HEADER_TAG: &str = "p1proto-settings";
+const LATEST_VERSION: u32 = 1;
+const COMMENT_PREFIX: char = '#';
+
+const BGM_ENABLED: &str = "bgm_enabled";
+const BGM_VOLUME: &str = "bgm_volume";
+const SFX_ENABLED: &str = "sfx_enabled";
+const SFX_VOLUME: &str = "sfx_volume";
+const WINDOW_MODE: &str = "window_mode";
+const WINDOW_SCALE: &str = "window_scale";
+const FULL_JUMP_ON_TAP: &str = "full_jump_on_tap";
+const DISABLE_QUICK_RESPAWN: &str = "disable_quick_respawn";
+const BIND_TAG: &str = "bind";
+
+/// Actions the player may rebind; other `project.godot` actions stay fixed.
+pub const REBINDABLE_ACTIONS: [&str; 7] = [
+    "act_walk_left",
+    "act_walk_right",
+    "act_up",
+    "act_down",
+    "act_jump",
+    "act_dash",
+    "act_respawn",
+];
+
+pub const MIN_WINDOW_SCALE: u32 = 1;
+pub const MAX_WINDOW_SCALE: u32 = 8;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Settings {
+    pub audio: AudioSettings,
+    pub video: VideoSettings,
+    pub key_bindings: KeyBindings,
+    pub accessibility: AccessibilitySettings,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioSettings {
+    pub bgm_enabled: bool,
+    /// Linear volume in `0.0..=1.0`.
+    pub bgm_volume: f32,
+    pub sfx_enabled: bool,
+    pub sfx_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            // BGM stays off until the player turns it on.
+            bgm_enabled: false,
+            bgm_volume: 1.0,
+            sfx_enabled: true,
+            sfx_volume: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum WindowMode {
+    #[default]
+    Windowed,
+    Fullscreen,
+}
+
+impl WindowMode {
+    pub const ALL: [Self; 2] = [Self::Windowed, Self::Fullscreen];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Windowed => "windowed",
+            Self::Fullscreen => "fullscreen",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VideoSettings {
+    pub window_mode: WindowMode,
+    /// Windowed size as a multiple of the 320x240 viewport.
+    pub window_scale: u32,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowMode::Windowed,
+            window_scale: 4,
+        }
+    }
+}
+
+/// Key overrides per rebindable action, as Godot physical keycodes. Actions
+/// without an entry keep their `project.godot` events.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyBindings {
+    keys: BTreeMap<String, Vec<i64>>,
+}
+
+impl KeyBindings {
+    pub fn keys(&self, action: &str) -> Option<&[i64]> {
+        self.keys.get(action).map(Vec::as_slice)
+    }
+
+    /// Replaces the keys for `action`. Rejects actions that are not
+    /// rebindable and empty or non-positive keycode lists.
+    pub fn bind(&mut self, action: &str, keys: &[i64]) -> bool {
+        if !REBINDABLE_ACTIONS.contains(&action)
+            || keys.is_empty()
+            || keys.iter().any(|&key| key <= 0)
+        {
+            return false;
+        }
+
+        let mut unique = Vec::with_capacity(keys.len());
+        for &key in keys {
+            if !unique.contains(&key) {
+                unique.push(key);
+            }
+        }
+        self.keys.insert(action.to_string(), unique);
+        true
+    }
+
+    /// Returns `action` to its project default.
+    pub fn reset(&mut self, action: &str) -> bool {
+        self.keys.remove(action).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[i64])> {
+        self.keys
+            .iter()
+            .map(|(action, keys)| (action.as_str(), keys.as_slice()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AccessibilitySettings {
+    /// Releasing jump early no longer cuts the jump short.
+    pub full_jump_on_tap: bool,
+    /// Ignores the quick respawn action so it cannot be pressed by accident.
+    pub disable_quick_respawn: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SettingsError {
+    MissingHeader,
+    UnsupportedVersion(u32),
+    Malformed { line: usize, content: String },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => {
+                write!(f, "settings file does not start with a {HEADER_TAG} header")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "settings file version {version} is not supported")
+            }
+            Self::Malformed { line, content } => {
+                write!(f, "malformed setting on line {line}: {content}")
+            }
+        }
+    }
+}
+
+/// Clamps a linear volume into `0.0..=1.0`; non-finite values become 1.0.
+pub fn clamp_volume(volume: f32) -> f32 {
+    if volume.is_finite() {
+        volume.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+pub fn clamp_window_scale(scale: u32) -> u32 {
+    scale.clamp(MIN_WINDOW_SCALE, MAX_WINDOW_SCALE)
+}
+
+impl Settings {
+    pub fn encode(&self) -> String {
+        let audio = &self.audio;
+        let video = &self.video;
+        let accessibility = &self.accessibility;
+
+        let mut out = format!("{HEADER_TAG} {LATEST_VERSION}\n");
+        out.push_str(&format!("{BGM_ENABLED} {}\n", audio.bgm_enabled));
+        out.push_str(&format!("{BGM_VOLUME} {}\n", audio.bgm_volume));
+        out.push_str(&format!("{SFX_ENABLED} {}\n", audio.sfx_enabled));
+        out.push_str(&format!("{SFX_VOLUME} {}\n", audio.sfx_volume));
+        out.push_str(&format!("{WINDOW_MODE} {}\n", video.window_mode.name()));
+        out.push_str(&format!("{WINDOW_SCALE} {}\n", video.window_scale));
+        out.push_str(&format!(
+            "{FULL_JUMP_ON_TAP} {}\n",
+            accessibility.full_jump_on_tap
+        ));
+        out.push_str(&format!(
+            "{DISABLE_QUICK_RESPAWN} {}\n",
+            accessibility.disable_quick_respawn
+        ));
+        for (action, keys) in self.key_bindings.iter() {
+            let keys: Vec<String> = keys.iter().map(i64::to_string).collect();
+            out.push_str(&format!("{BIND_TAG} {action} {}\n", keys.join(" ")));
+        }
+        out
+    }
+
+    pub fn decode(text: &str) -> Result<Self, SettingsError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with(COMMENT_PREFIX));
+
+        let Some((header_line, header)) = lines.next() else {
+            return Err(SettingsError::MissingHeader);
+        };
+        let version = parse_header(header_line, header)?;
+        if version > LATEST_VERSION {
+            return Err(SettingsError::UnsupportedVersion(version));
+        }
+
+        let mut settings = Self::default();
+        for (line, content) in lines {
+            let tokens: Vec<&str> = content.split_whitespace().collect();
+            if !settings.apply_record(&tokens) {
+                return Err(SettingsError::Malformed {
+                    line,
+                    content: content.to_string(),
+                });
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Returns false for a known setting with a bad value.
+    fn apply_record(&mut self, tokens: &[&str]) -> bool {
+        match tokens {
+            [BGM_ENABLED, value] => parse_into(value, &mut self.audio.bgm_enabled),
+            [SFX_ENABLED, value] => parse_into(value, &mut self.audio.sfx_enabled),
+            [BGM_VOLUME, value] => parse_volume(value, &mut self.audio.bgm_volume),
+            [SFX_VOLUME, value] => parse_volume(value, &mut self.audio.sfx_volume),
+            [WINDOW_MODE, name] => WindowMode::from_name(name)
+                .map(|mode| self.video.window_mode = mode)
+                .is_some(),
+            [WINDOW_SCALE, value] => value
+                .parse()
+                .ok()
+                .filter(|scale| (MIN_WINDOW_SCALE..=MAX_WINDOW_SCALE).contains(scale))
+                .map(|scale| self.video.window_scale = scale)
+                .is_some(),
+            [FULL_JUMP_ON_TAP, value] => {
+                parse_into(value, &mut self.accessibility.full_jump_on_tap)
+            }
+            [DISABLE_QUICK_RESPAWN, value] => {
+                parse_into(value, &mut self.accessibility.disable_quick_respawn)
+            }
+            [BIND_TAG, action, keys @ ..] => {
+                let keys: Option<Vec<i64>> = keys.iter().map(|key| key.parse().ok()).collect();
+                keys.is_some_and(|keys| self.key_bindings.bind(action, &keys))
+            }
+            [
+                BGM_ENABLED
+                | SFX_ENABLED
+                | BGM_VOLUME
+                | SFX_VOLUME
+                | WINDOW_MODE
+                | WINDOW_SCALE
+                | FULL_JUMP_ON_TAP
+                | DISABLE_QUICK_RESPAWN
+                | BIND_TAG,
+                ..,
+            ] => false,
+            _ => true,
+        }
+    }
+}
+
+fn parse_header(line_number: usize, header: &str) -> Result<u32, SettingsError> {
+    let mut tokens = header.split_whitespace();
+    if tokens.next() != Some(HEADER_TAG) {
+        return Err(SettingsError::MissingHeader);
+    }
+
+    match (tokens.next().and_then(|v| v.parse().ok()), tokens.next()) {
+        (Some(version), None) => Ok(version),
+        _ => Err(SettingsError::Malformed {
+            line: line_number,
+            content: header.to_string(),
+        }),
+    }
+}
+
+fn parse_into(value: &str, target: &mut bool) -> bool {
+    value.parse().map(|value| *target = value).is_ok()
+}
+
+fn parse_volume(value: &str, target: &mut f32) -> bool {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|volume| (0.0..=1.0).contains(volume))
+        .map(|volume| *target = volume)
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrips_every_setting() {
+        let mut settings = Settings::default();
+        settings.audio.bgm_enabled = true;
+        settings.audio.bgm_volume = 0.5;
+        settings.audio.sfx_volume = 0.25;
+        settings.video.window_mode = WindowMode::Fullscreen;
+        settings.video.window_scale = 2;
+        settings.accessibility.full_jump_on_tap = true;
+        assert!(settings.key_bindings.bind("act_jump", &[32, 90]));
+
+        assert_eq!(Settings::decode(&settings.encode()), Ok(settings));
+    }
+
+    #[test]
+    fn missing_settings_keep_defaults_and_unknown_ones_are_skipped() {
+        let settings =
+            Settings::decode("p1proto-settings 1\nbgm_volume 0.75\nscreen_shake off\n").unwrap();
+
+        assert_eq!(settings.audio.bgm_volume, 0.75);
+        assert!(!settings.audio.bgm_enabled);
+        assert_eq!(settings.video, VideoSettings::default());
+    }
+
+    #[test]
+    fn rejects_bad_values_and_headers() {
+        assert_eq!(
+            Settings::decode("p1proto-settings 1\nwindow_scale 0\n"),
+            Err(SettingsError::Malformed {
+                line: 2,
+                content: "window_scale 0".to_string(),
+            })
+        );
+        assert_eq!(
+            Settings::decode("p1proto-settings 1\nbind ui_esc 4194305\n"),
+            Err(SettingsError::Malformed {
+                line: 2,
+                content: "bind ui_esc 4194305".to_string(),
+            })
+        );
+        assert_eq!(
+            Settings::decode("p1proto-settings 2\n"),
+            Err(SettingsError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            Settings::decode("p1proto-save 5\n"),
+            Err(SettingsError::MissingHeader)
+        );
+    }
+
+    #[test]
+    fn bindings_only_accept_rebindable_actions() {
+        let mut bindings = KeyBindings::default();
+
+        assert!(!bindings.bind("ui_esc", &[4194305]));
+        assert!(!bindings.bind("act_jump", &[]));
+        assert!(bindings.bind("act_jump", &[90, 32, 90]));
+        assert_eq!(bindings.keys("act_jump"), Some(&[90, 32][..]));
+        assert!(bindings.reset("act_jump"));
+        assert_eq!(bindings.keys("act_jump"), None);
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt;
 
 use godot::prelude::*;
 
-use super::progress;
+use super::progress::ProgressRepository;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct RoomId {
@@ -81,8 +81,14 @@ impl SpawnResolver {
         }
     }
 
-    pub fn resolve(&self, mut room_exists: impl FnMut(RoomId) -> bool) -> SpawnPoint {
-        if let Some(snapshot) = progress::take_pending_load()
+    /// Consumes the pending load in `progress`, falling back to the initial
+    /// spawn when there is none or its room no longer exists.
+    pub fn resolve(
+        &self,
+        progress: &mut ProgressRepository,
+        mut room_exists: impl FnMut(RoomId) -> bool,
+    ) -> SpawnPoint {
+        if let Some(snapshot) = progress.take_pending_load()
             && room_exists(snapshot.room)
         {
             return SpawnPoint {
@@ -179,7 +185,7 @@ impl BoundaryDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::progress;
+    use crate::core::progress::DEFAULT_SAVE_SLOT;
 
     fn room(x: i32, y: i32) -> RoomId {
         RoomId::new(x, y)
@@ -187,19 +193,16 @@ mod tests {
 
     #[test]
     fn falls_back_to_initial_spawn_when_saved_room_is_missing() {
-        progress::reset_all();
-        progress::save_checkpoint(
-            progress::DEFAULT_SAVE_SLOT,
-            room(9, 9),
-            Vector2::new(1.0, 2.0),
-        );
-        assert!(progress::queue_load(progress::DEFAULT_SAVE_SLOT));
+        let mut progress = ProgressRepository::default();
+        progress.save_checkpoint(DEFAULT_SAVE_SLOT, room(9, 9), Vector2::new(1.0, 2.0), None);
+        assert!(progress.queue_load(DEFAULT_SAVE_SLOT));
 
         let resolver = SpawnResolver::new(room(0, 1), Vector2::new(64.0, 64.0));
-        let spawn = resolver.resolve(|candidate| candidate != room(9, 9));
+        let spawn = resolver.resolve(&mut progress, |candidate| candidate != room(9, 9));
 
         assert_eq!(spawn.room, room(0, 1));
         assert_eq!(spawn.position, Vector2::new(64.0, 64.0));
+        assert_eq!(progress.take_pending_load(), None);
     }
 
     #[test]
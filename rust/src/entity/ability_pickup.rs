@@ -0,0 +1,91 @@
+use godot::classes::{AnimatedSprite2D, Area2D, IArea2D, Node};
+use godot::prelude::*;
+
+use crate::core::progress::PersistentEntityKind;
+use crate::core::progress::abilities::Ability;
+
+use super::persistence::PersistentEntityRef;
+
+/// Grants a movement ability on touch. Collected pickups stay gone and their
+/// ability stays unlocked, whatever checkpoint the player returns to.
+#[derive(GodotClass)]
+#[class(base=Area2D)]
+pub struct AbilityPickup {
+    #[base]
+    base: Base<Area2D>,
+    sprite: OnReady<Gd<AnimatedSprite2D>>,
+    #[export]
+    room_coords: Vector2i,
+    /// Ability name as used in save files, e.g. "double_jump".
+    #[export]
+    ability: GString,
+    original_position: Vector2,
+    persistent_entity: Option<PersistentEntityRef>,
+}
+
+#[godot_api]
+impl IArea2D for AbilityPickup {
+    fn init(base: Base<Area2D>) -> Self {
+        Self {
+            base,
+            sprite: OnReady::from_node("AnimatedSprite2D"),
+            room_coords: Vector2i::ZERO,
+            ability: GString::new(),
+            original_position: Vector2::ZERO,
+            persistent_entity: None,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.original_position = self.base().get_global_position();
+        if self.granted_ability().is_none() {
+            godot_warn!(
+                "[AbilityPickup] unknown ability {:?} at {:?}, room {:?}",
+                self.ability,
+                self.original_position,
+                self.room_coords
+            );
+        }
+
+        let node = self.to_gd().upcast::<Node>();
+        let persistent_entity =
+            PersistentEntityRef::new(&node, self.room_coords, self.original_position);
+
+        if persistent_entity.is_marked(PersistentEntityKind::AbilityPickup) {
+            self.base_mut().queue_free();
+            return;
+        }
+        self.persistent_entity = Some(persistent_entity);
+
+        self.sprite.play();
+        self.signals()
+            .body_entered()
+            .connect_self(Self::on_body_entered);
+    }
+}
+
+#[godot_api]
+impl AbilityPickup {
+    #[signal]
+    pub fn ability_collected(ability: GString);
+
+    #[func]
+    fn on_body_entered(&mut self, _body: Gd<Node2D>) {
+        let Some(ability) = self.granted_ability() else {
+            return;
+        };
+        let Some(persistent_entity) = self.persistent_entity.clone() else {
+            return;
+        };
+
+        let _granted = persistent_entity.grant_ability(ability);
+        godot_print!("[AbilityPickup] unlocked {}", ability.name());
+        let name = GString::from(ability.name());
+        self.signals().ability_collected().emit(&name);
+        self.base_mut().queue_free();
+    }
+
+    fn granted_ability(&self) -> Option<Ability> {
+        Ability::from_name(&self.ability.to_string())
+    }
+}
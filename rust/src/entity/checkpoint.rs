@@ -1,7 +1,9 @@
 use godot::classes::{AnimatedSprite2D, Area2D, IArea2D, Node};
 use godot::prelude::*;
 
-use super::persistence::PersistentEntityRef;
+use crate::save;
+
+use super::persistence::{self, PersistentEntityRef};
 
 const POSITION_MATCH_EPSILON: f32 = 1.0;
 
@@ -69,7 +71,9 @@ impl Checkpoint {
         self.signals()
             .checkpoint_activated()
             .emit(room_coords, position);
-        let _snapshot = persistent_entity.save_checkpoint();
+        persistence::save_runtime_state(&mut self.base().get_tree());
+        let (slot, _snapshot) = persistent_entity.save_checkpoint();
+        let _written = save::write_slot(slot);
     }
 
     #[func]
@@ -1,3 +1,4 @@
+pub mod ability_pickup;
 pub mod checkpoint;
 pub mod collectible_star;
 pub mod crumbling_platform;
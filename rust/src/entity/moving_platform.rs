@@ -1,6 +1,6 @@
 use godot::{
     builtin::Variant,
-    classes::{AnimatableBody2D, IAnimatableBody2D, Object, Tween, tween},
+    classes::{AnimatableBody2D, Engine, IAnimatableBody2D, Object, Tween, tween},
     prelude::*,
 };
 
@@ -25,6 +25,11 @@ pub struct MovingPlatform {
 
     start_position: Vector2,
     tween: Option<Gd<Tween>>,
+    /// Measured from the position change each physics tick, so it follows
+    /// the tween's easing rather than its end points.
+    velocity: Vector2,
+    last_global_position: Vector2,
+    last_physics_frame: u64,
 }
 
 #[godot_api]
@@ -37,13 +42,31 @@ impl IAnimatableBody2D for MovingPlatform {
             pause_time: 0.0,
             start_position: Vector2::ZERO,
             tween: None,
+            velocity: Vector2::ZERO,
+            last_global_position: Vector2::ZERO,
+            last_physics_frame: 0,
         }
     }
 
     fn ready(&mut self) {
         self.start_position = self.base().get_position();
+        self.resync();
         self.start_motion();
     }
+
+    fn physics_process(&mut self, delta: f64) {
+        let position = self.base().get_global_position();
+        let frame = Engine::singleton().get_physics_frames();
+        // The tween keeps running while the tree is paused but this callback
+        // does not, so after a pause the position delta spans many ticks; keep
+        // the last velocity for that tick instead.
+        let resumed = frame > self.last_physics_frame + 1;
+        if delta > 0.0 && !resumed {
+            self.velocity = (position - self.last_global_position) / delta as f32;
+        }
+        self.last_global_position = position;
+        self.last_physics_frame = frame;
+    }
 }
 
 #[godot_api]
@@ -52,9 +75,23 @@ impl MovingPlatform {
     #[func]
     fn restart(&mut self) {
         self.start_position = self.base().get_position();
+        self.resync();
+        self.velocity = Vector2::ZERO;
         self.start_motion();
     }
 
+    /// Velocity over the last physics tick, in pixels per second.
+    #[func]
+    pub fn get_platform_velocity(&self) -> Vector2 {
+        self.velocity
+    }
+
+    /// Measure the next tick's velocity from where the platform is now.
+    fn resync(&mut self) {
+        self.last_global_position = self.base().get_global_position();
+        self.last_physics_frame = Engine::singleton().get_physics_frames();
+    }
+
     fn start_motion(&mut self) {
         // Replace any previously running tween so only one controls the platform.
         if let Some(mut tween) = self.tween.take() {
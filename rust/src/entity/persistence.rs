@@ -1,55 +1,131 @@
-use godot::{classes::Node, prelude::*};
+use godot::{
+    classes::{Node, SceneTree},
+    prelude::*,
+};
 
+use crate::core::progress::abilities::Ability;
+use crate::core::progress::entity_state::EntityState;
 use crate::core::progress::{
-    self, DEFAULT_SAVE_SLOT, PersistentEntityKind, PersistentKey, SaveSnapshot,
+    self, PersistentEntityKind, PersistentKey, ProgressContext, SaveSlot, SaveSnapshot,
 };
 use crate::core::world::RoomId;
 
 const LDTK_IID_META: &str = "ldtk_iid";
 
 pub(crate) const PLAIN_KEY_GROUP: &str = "plain_keys";
+pub(crate) const PUSHABLE_CRATE_GROUP: &str = "pushable_crates";
+
+/// Entities that keep state in the scene and only copy it into progress when
+/// asked, through a `save_runtime_state` method.
+pub(crate) const RUNTIME_STATE_GROUP: &str = "runtime_state";
+const SAVE_RUNTIME_STATE_METHOD: &str = "save_runtime_state";
 
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct PersistentEntityRef {
     pub room: RoomId,
     pub position: Vector2,
     key: PersistentKey,
+    progress: ProgressContext,
 }
 
 impl PersistentEntityRef {
+    /// Refers to the entity in the global progress shared by the game.
     pub(crate) fn new(node: &Gd<Node>, room_coords: Vector2i, position: Vector2) -> Self {
+        Self::with_progress(progress::global(), node, room_coords, position)
+    }
+
+    pub(crate) fn with_progress(
+        progress: ProgressContext,
+        node: &Gd<Node>,
+        room_coords: Vector2i,
+        position: Vector2,
+    ) -> Self {
         Self {
             room: room_id(room_coords),
             position,
             key: persistent_key(node, room_coords, position),
+            progress,
         }
     }
 
+    pub(crate) fn has_ldtk_iid(&self) -> bool {
+        matches!(self.key, PersistentKey::Explicit(_))
+    }
+
     pub(crate) fn is_marked(&self, kind: PersistentEntityKind) -> bool {
-        progress::has_entity_key(kind, &self.key)
+        self.progress
+            .read(|repository| repository.has_entity_key(kind, &self.key))
     }
 
     pub(crate) fn mark(&self, kind: PersistentEntityKind) -> bool {
-        progress::mark_entity_key(kind, self.key.clone())
+        self.progress
+            .write(|repository| repository.mark_entity_key(kind, self.key.clone()))
+    }
+
+    /// Marks this ability pickup as collected and unlocks `ability` in the
+    /// same update, so subscribers never see one without the other.
+    pub(crate) fn grant_ability(&self, ability: Ability) -> bool {
+        self.progress.write(|repository| {
+            let marked =
+                repository.mark_entity_key(PersistentEntityKind::AbilityPickup, self.key.clone());
+            repository.unlock_ability(ability) || marked
+        })
     }
 
-    pub(crate) fn save_checkpoint(&self) -> SaveSnapshot {
-        progress::save_checkpoint_key(
-            DEFAULT_SAVE_SLOT,
-            self.room,
-            self.position,
-            self.key.clone(),
-        )
+    /// Reads a typed field stored for this entity in the active slot.
+    pub(crate) fn get_state<T: EntityState>(&self, field: &str) -> Option<T> {
+        self.progress
+            .read(|repository| repository.entity_state(&self.key, field))
+            .and_then(|value| T::from_state(&value))
+    }
+
+    /// Stores a typed field for this entity. Returns false when `field` or the
+    /// value cannot be saved (e.g. names with whitespace).
+    pub(crate) fn set_state<T: EntityState>(&self, field: &str, value: &T) -> bool {
+        let stored = self.progress.write(|repository| {
+            repository.set_entity_state(self.key.clone(), field, value.to_state())
+        });
+        if !stored {
+            godot_warn!(
+                "[Persistence] rejected state field {:?} for {:?}",
+                field,
+                self.key
+            );
+        }
+        stored
+    }
+
+    pub(crate) fn clear_state(&self, field: &str) -> bool {
+        self.progress
+            .write(|repository| repository.clear_entity_state(&self.key, field))
+    }
+
+    /// Saves a checkpoint at this entity in the active slot and returns that
+    /// slot, which the caller writes to disk if its progress is persisted.
+    pub(crate) fn save_checkpoint(&self) -> (SaveSlot, SaveSnapshot) {
+        self.progress.write(|repository| {
+            let slot = repository.active_slot();
+            let snapshot =
+                repository.save_checkpoint(slot, self.room, self.position, Some(self.key.clone()));
+            (slot, snapshot)
+        })
     }
 
     pub(crate) fn find_saved_checkpoint(&self, match_epsilon: f32) -> Option<SaveSnapshot> {
-        let snapshot = progress::peek_checkpoint(DEFAULT_SAVE_SLOT)?;
+        let snapshot = self
+            .progress
+            .read(|repository| repository.peek_checkpoint(repository.active_slot()))?;
         snapshot
             .matches_checkpoint(self.room, self.position, match_epsilon, Some(&self.key))
             .then_some(snapshot)
     }
 }
 
+/// Copies scene-held entity state into progress before a slot is written.
+pub(crate) fn save_runtime_state(tree: &mut Gd<SceneTree>) {
+    tree.call_group(RUNTIME_STATE_GROUP, SAVE_RUNTIME_STATE_METHOD, &[]);
+}
+
 fn room_id(room_coords: Vector2i) -> RoomId {
     RoomId::from(room_coords)
 }
@@ -20,6 +20,11 @@ pub struct PressurePlate {
     /// NodePath to the target SwitchDoor
     #[export]
     target_id: NodePath,
+
+    /// If true, the plate stays pressed once activated and latches the target
+    /// door open across room visits
+    #[export]
+    latched: bool,
 }
 
 #[godot_api]
@@ -31,6 +36,7 @@ impl IArea2D for PressurePlate {
             sprite: OnReady::from_node("AnimatedSprite2D"),
             target_room: Vector2i::default(),
             target_id: NodePath::default(),
+            latched: false,
         }
     }
 
@@ -45,6 +51,11 @@ impl IArea2D for PressurePlate {
         self.signals()
             .body_exited()
             .connect_self(Self::on_body_exited);
+
+        if self.latched {
+            // The door may be ready after this plate; check once the room is in the tree
+            self.base_mut().call_deferred("sync_with_latched_door", &[]);
+        }
     }
 }
 
@@ -61,7 +72,11 @@ impl PressurePlate {
 
             // Open target door
             if let Some(mut door) = self.get_target_door() {
-                door.bind_mut().open();
+                let mut door = door.bind_mut();
+                if self.latched {
+                    door.latch();
+                }
+                door.open();
             }
         }
     }
@@ -75,8 +90,8 @@ impl PressurePlate {
     /// Check if pressure plate should deactivate (called deferred)
     #[func]
     fn check_deactivation(&mut self) {
-        // Only deactivate if no bodies remain on the plate
-        if self.pressed && self.base().get_overlapping_bodies().is_empty() {
+        // Latched plates stay down; only deactivate if no bodies remain on the plate
+        if !self.latched && self.pressed && self.base().get_overlapping_bodies().is_empty() {
             self.pressed = false;
             self.sprite.set_animation("inactive");
             self.sprite.stop();
@@ -89,6 +104,23 @@ impl PressurePlate {
         }
     }
 
+    /// Latch the target door and show the plate pressed when the door was
+    /// latched open on an earlier visit
+    #[func]
+    fn sync_with_latched_door(&mut self) {
+        let door_latched_open = self.get_target_door().is_some_and(|mut door| {
+            let mut door = door.bind_mut();
+            door.latch();
+            door.is_latched_open()
+        });
+
+        if door_latched_open && !self.pressed {
+            self.pressed = true;
+            self.sprite.set_animation("active");
+            self.sprite.play();
+        }
+    }
+
     #[func]
     pub fn is_pressed(&self) -> bool {
         self.pressed
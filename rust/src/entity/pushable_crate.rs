@@ -1,20 +1,112 @@
-use godot::classes::{IRigidBody2D, RigidBody2D};
+use godot::classes::{
+    IRigidBody2D, Node, Node2D, PhysicsDirectBodyState2D, RigidBody2D, SceneTree,
+};
 use godot::prelude::*;
 
+use crate::core::player::is_floor_normal;
+
+use super::moving_platform::MovingPlatform;
+use super::persistence::{PUSHABLE_CRATE_GROUP, PersistentEntityRef, RUNTIME_STATE_GROUP};
+
+/// Entity state fields recorded for crates with an LDtk IID.
+const RESTING_POSITION_FIELD: &str = "resting_position";
+const FROZEN_FIELD: &str = "frozen";
+const RESET_METHOD: &str = "reset_to_spawn";
+/// Steepest contact that counts as ground, matching the player's slopes.
+const FLOOR_MAX_ANGLE_DEGREES: f32 = 46.0;
+/// Cap on the platform velocity a crate rides with, like
+/// `MovementConfig::max_inherited_platform_speed` for the player.
+const MAX_CARRIED_PLATFORM_SPEED: f32 = 240.0;
+
+/// Resets every crate in the loaded room, e.g. from the pause menu after a softlock.
+pub(crate) fn reset_all_crates(tree: &mut Gd<SceneTree>) {
+    tree.call_group(PUSHABLE_CRATE_GROUP, RESET_METHOD, &[]);
+}
+
 #[derive(GodotClass)]
 #[class(base=RigidBody2D)]
 pub struct PushableCrate {
     #[base]
     base: Base<RigidBody2D>,
+    #[export]
+    room_coords: Vector2i,
+    /// Position the crate was placed at in LDtk, used by `reset_to_spawn`
+    authored_position: Vector2,
+    persistent_entity: Option<PersistentEntityRef>,
+    /// Applied in `integrate_forces`, where moving a live body is safe.
+    pending_teleport: Option<Teleport>,
+    /// Platform velocity included in the body's velocity since the last
+    /// physics step, so the crate's own motion can be told apart from it.
+    carried_velocity: Vector2,
+}
+
+struct Teleport {
+    position: Vector2,
+    frozen: bool,
 }
 
 #[godot_api]
 impl IRigidBody2D for PushableCrate {
     fn init(base: Base<RigidBody2D>) -> Self {
-        Self { base }
+        Self {
+            base,
+            room_coords: Vector2i::ZERO,
+            authored_position: Vector2::ZERO,
+            persistent_entity: None,
+            pending_teleport: None,
+            carried_velocity: Vector2::ZERO,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.authored_position = self.base().get_position();
+        self.base_mut().add_to_group(PUSHABLE_CRATE_GROUP);
+        self.base_mut().add_to_group(RUNTIME_STATE_GROUP);
+        self.persistent_entity = self.build_persistent_entity();
+        self.restore_if_saved();
+    }
+
+    /// Rides moving platforms; friction alone lets the damped crate slide off.
+    /// The crate keeps its own velocity relative to the platform, so pushes
+    /// and friction still act on top of the ride.
+    fn integrate_forces(&mut self, state: Option<Gd<PhysicsDirectBodyState2D>>) {
+        let Some(mut state) = state else {
+            return;
+        };
+        if let Some(teleport) = self.pending_teleport.take() {
+            self.carried_velocity = Vector2::ZERO;
+            self.apply_teleport(&mut state, teleport);
+            return;
+        }
+
+        let platform_velocity = supporting_platform_velocity(&state)
+            .map(|velocity| velocity.limit_length(Some(MAX_CARRIED_PLATFORM_SPEED)));
+        let Some(platform_velocity) = platform_velocity else {
+            // Off the platform the carried velocity stays on as momentum.
+            self.carried_velocity = Vector2::ZERO;
+            return;
+        };
+
+        let relative_velocity = state.get_linear_velocity() - self.carried_velocity;
+        state.set_linear_velocity(relative_velocity + platform_velocity);
+        self.carried_velocity = platform_velocity;
     }
 }
 
+fn supporting_platform_velocity(state: &Gd<PhysicsDirectBodyState2D>) -> Option<Vector2> {
+    let floor_max_angle = FLOOR_MAX_ANGLE_DEGREES.to_radians();
+    (0..state.get_contact_count()).find_map(|index| {
+        if !is_floor_normal(state.get_contact_local_normal(index), floor_max_angle) {
+            return None;
+        }
+        let platform = state
+            .get_contact_collider_object(index)?
+            .try_cast::<MovingPlatform>()
+            .ok()?;
+        Some(platform.bind().get_platform_velocity())
+    })
+}
+
 #[godot_api]
 impl PushableCrate {
     #[func]
@@ -31,4 +123,87 @@ impl PushableCrate {
     fn is_frozen(&self) -> bool {
         self.base().is_freeze_enabled()
     }
+
+    /// Record the current position and frozen state in progress
+    #[func]
+    fn save_runtime_state(&self) {
+        let Some(persistent_entity) = &self.persistent_entity else {
+            return;
+        };
+
+        // A teleport still waiting for the next physics step is where the crate is headed
+        let (position, frozen) = match &self.pending_teleport {
+            Some(teleport) => (teleport.position, teleport.frozen),
+            None => (self.base().get_position(), self.is_frozen()),
+        };
+        let _position_saved = persistent_entity.set_state(RESTING_POSITION_FIELD, &position);
+        let _frozen_saved = persistent_entity.set_state(FROZEN_FIELD, &frozen);
+    }
+
+    /// Move the crate back to its authored spot and forget any saved state.
+    /// Used to recover from softlocks.
+    #[func]
+    pub fn reset_to_spawn(&mut self) {
+        if let Some(persistent_entity) = &self.persistent_entity {
+            let _position_cleared = persistent_entity.clear_state(RESTING_POSITION_FIELD);
+            let _frozen_cleared = persistent_entity.clear_state(FROZEN_FIELD);
+        }
+
+        let position = self.authored_position;
+        self.queue_teleport(position, false);
+        godot_print!("[PushableCrate] reset to {:?}", position);
+    }
+
+    fn restore_if_saved(&mut self) {
+        let Some(persistent_entity) = &self.persistent_entity else {
+            return;
+        };
+
+        let position = persistent_entity.get_state::<Vector2>(RESTING_POSITION_FIELD);
+        let frozen = persistent_entity.get_state::<bool>(FROZEN_FIELD);
+        if position.is_none() && frozen.is_none() {
+            return;
+        }
+
+        let position = position.unwrap_or(self.authored_position);
+        self.queue_teleport(position, frozen.unwrap_or(false));
+    }
+
+    /// Frozen bodies skip `integrate_forces`, so the crate thaws until the
+    /// teleport lands and refreezes afterwards if asked to.
+    fn queue_teleport(&mut self, position: Vector2, frozen: bool) {
+        self.pending_teleport = Some(Teleport { position, frozen });
+        self.base_mut().set_freeze_enabled(false);
+    }
+
+    fn apply_teleport(&mut self, state: &mut Gd<PhysicsDirectBodyState2D>, teleport: Teleport) {
+        let mut transform = state.get_transform();
+        transform.origin = self.global_position_of(teleport.position);
+        state.set_transform(transform);
+        state.set_linear_velocity(Vector2::ZERO);
+        state.set_angular_velocity(0.0);
+        if teleport.frozen {
+            self.base_mut().set_deferred("freeze", &true.to_variant());
+        }
+    }
+
+    /// Saved and authored positions are relative to the room.
+    fn global_position_of(&self, position: Vector2) -> Vector2 {
+        self.base()
+            .get_parent()
+            .and_then(|parent| parent.try_cast::<Node2D>().ok())
+            .map_or(position, |parent| parent.to_global(position))
+    }
+
+    /// Only crates placed in LDtk have a stable identity to save under.
+    fn build_persistent_entity(&self) -> Option<PersistentEntityRef> {
+        let persistent_entity = PersistentEntityRef::new(
+            &self.to_gd().upcast::<Node>(),
+            self.room_coords,
+            self.authored_position,
+        );
+        persistent_entity
+            .has_ldtk_iid()
+            .then_some(persistent_entity)
+    }
 }
@@ -1,6 +1,11 @@
-use godot::classes::{AnimatedSprite2D, CollisionShape2D, IStaticBody2D, StaticBody2D};
+use godot::classes::{AnimatedSprite2D, CollisionShape2D, IStaticBody2D, Node, StaticBody2D};
 use godot::prelude::*;
 
+use super::persistence::PersistentEntityRef;
+
+/// Entity state field set once a latched door has fully opened.
+const LATCHED_OPEN_FIELD: &str = "latched_open";
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DoorState {
     Closed,
@@ -29,6 +34,11 @@ pub struct SwitchDoor {
     /// If true, the door starts in the open state
     #[export]
     starts_open: bool,
+
+    /// If true, the door stays open once it has fully opened and is restored
+    /// open on later visits
+    #[export]
+    latched: bool,
 }
 
 #[godot_api]
@@ -41,6 +51,7 @@ impl IStaticBody2D for SwitchDoor {
             collision: OnReady::from_node("CollisionShape2D"),
             room_coords: Vector2i::ZERO,
             starts_open: false,
+            latched: false,
         }
     }
 
@@ -59,7 +70,7 @@ impl IStaticBody2D for SwitchDoor {
         }
 
         // Set initial state
-        if self.starts_open {
+        if self.starts_open || self.was_latched_open() {
             self.state = DoorState::Open;
             self.sprite.set_animation("open");
             self.collision.set_disabled(true);
@@ -98,9 +109,14 @@ impl SwitchDoor {
         }
     }
 
-    /// Close the door (if not already closed or closing)
+    /// Close the door (if not already closed or closing). A latched door that
+    /// has fully opened stays open.
     #[func]
     pub fn close(&mut self) {
+        if self.is_latched_open() {
+            return;
+        }
+
         match self.state {
             DoorState::Open | DoorState::Opening => {
                 self.state = DoorState::Closing;
@@ -132,6 +148,52 @@ impl SwitchDoor {
         self.state == DoorState::Closed
     }
 
+    /// Opt into latching at runtime (used by latched pressure plates). A door
+    /// that was latched open on an earlier visit opens right away.
+    #[func]
+    pub fn latch(&mut self) {
+        self.latched = true;
+        match self.state {
+            DoorState::Open => self.record_latched_open(),
+            DoorState::Closed if self.was_latched_open() => {
+                self.state = DoorState::Open;
+                self.sprite.set_animation("open");
+                self.collision.set_deferred("disabled", &true.to_variant());
+            }
+            _ => {}
+        }
+    }
+
+    #[func]
+    pub fn is_latched_open(&self) -> bool {
+        self.latched && self.state == DoorState::Open
+    }
+
+    fn persistent_entity(&self) -> PersistentEntityRef {
+        PersistentEntityRef::new(
+            &self.to_gd().upcast::<Node>(),
+            self.room_coords,
+            self.base().get_position(),
+        )
+    }
+
+    fn was_latched_open(&self) -> bool {
+        self.latched
+            && self
+                .persistent_entity()
+                .get_state::<bool>(LATCHED_OPEN_FIELD)
+                .unwrap_or(false)
+    }
+
+    fn record_latched_open(&self) {
+        if self
+            .persistent_entity()
+            .set_state(LATCHED_OPEN_FIELD, &true)
+        {
+            godot_print!("[SwitchDoor] latched open");
+        }
+    }
+
     #[func]
     fn on_animation_finished(&mut self) {
         match self.state {
@@ -141,6 +203,9 @@ impl SwitchDoor {
                 self.sprite.set_animation("open");
                 // Disable collision when fully open (deferred to avoid physics query conflicts)
                 self.collision.set_deferred("disabled", &true.to_variant());
+                if self.latched {
+                    self.record_latched_open();
+                }
                 self.signals().door_opened().emit();
             }
             DoorState::Closing => {
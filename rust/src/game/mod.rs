@@ -1,6 +1,8 @@
 use godot::classes::{AudioStreamPlayer, Input};
 use godot::prelude::*;
 
+use crate::settings;
+
 mod player_spawner;
 mod portal_connector;
 pub mod room_manager;
@@ -31,9 +33,11 @@ impl INode for Game {
             .set_process_mode(godot::classes::node::ProcessMode::ALWAYS);
         self.base_mut().set_process(true);
 
-        // Default: BGM off unless player toggles it on.
+        // BGM follows the saved setting, which defaults to off.
         self.bgm_player.stop();
         self.bgm_enabled = false;
+        self.sync_bgm();
+        self.connect_settings();
     }
 
     fn process(&mut self, _delta: f64) {
@@ -46,13 +50,49 @@ impl INode for Game {
 
 #[godot_api]
 impl Game {
+    /// Goes through the `Settings` node so every `changed` listener hears it.
+    /// Deferred because `Game` is bound here and handles that signal itself.
     fn toggle_bgm(&mut self) {
-        self.bgm_enabled = !self.bgm_enabled;
-        if self.bgm_enabled {
+        let enabled = !settings::current().audio.bgm_enabled;
+        let node = self.to_gd().upcast::<Node>();
+        match settings::find_settings(&node) {
+            Some(mut settings) => {
+                settings.call_deferred("set_bgm_enabled", &[enabled.to_variant()]);
+            }
+            None => {
+                settings::update(|settings| settings.audio.bgm_enabled = enabled);
+                self.sync_bgm();
+            }
+        }
+        godot_print!("[Game] bgm_enabled: {}", enabled);
+    }
+
+    fn connect_settings(&mut self) {
+        let node = self.to_gd().upcast::<Node>();
+        let Some(settings) = settings::find_settings(&node) else {
+            godot_warn!("[Game] Settings autoload missing; BGM will not follow setting changes");
+            return;
+        };
+
+        let game = self.to_gd();
+        settings
+            .signals()
+            .changed()
+            .connect_other(&game, Self::sync_bgm);
+    }
+
+    #[func]
+    fn sync_bgm(&mut self) {
+        let enabled = settings::current().audio.bgm_enabled;
+        if enabled == self.bgm_enabled {
+            return;
+        }
+
+        self.bgm_enabled = enabled;
+        if enabled {
             self.bgm_player.play();
         } else {
             self.bgm_player.stop();
         }
-        godot_print!("[Game] bgm_enabled: {}", self.bgm_enabled);
     }
 }
@@ -3,14 +3,15 @@ use godot::prelude::*;
 
 use super::portal_connector::{connect_room_portal, find_portal_in_room};
 use super::room_runtime::{PlayerRuntime, RoomRuntime};
+use crate::core::progress::{self, statistics::DeathCause};
 use crate::core::session::{DeathPlan, RoomSession, RoomTransitionPlan, TransitionSpawn};
 use crate::core::world::{BoundaryDetector, RoomId, SpawnResolver};
-use crate::save::{self, DEFAULT_SAVE_SLOT};
+use crate::rooms::ROOM_SCENE_PATTERN;
+use crate::save;
 
 const INITIAL_ROOM: RoomId = RoomId::new(0, 1);
 const INITIAL_PLAYER_POS: Vector2 = Vector2::new(64.0, 64.0);
 const PLAYER_SCENE_PATH: &str = "res://player/player.tscn";
-const ROOM_SCENE_PATTERN: &str = "res://pipeline/ldtk/levels/Room_{x}_{y}.scn";
 const GAME_SCENE_PATH: &str = "res://game.tscn";
 const TRANSITION_THRESHOLD: f32 = 0.5;
 const ENTITY_LAYER_NAME: &str = "Entities";
@@ -24,6 +25,9 @@ pub struct GameRoomManager {
     initial_room: Vector2i,
     #[export]
     initial_player_pos: Vector2,
+    /// Writes the active slot whenever a room finishes loading.
+    #[export]
+    autosave_on_room_load: bool,
     room_runtime: RoomRuntime,
     player_runtime: PlayerRuntime,
     boundary_detector: BoundaryDetector,
@@ -38,21 +42,25 @@ impl INode2D for GameRoomManager {
             base,
             initial_room: INITIAL_ROOM.into(),
             initial_player_pos: INITIAL_PLAYER_POS,
+            autosave_on_room_load: true,
             room_runtime: RoomRuntime::new(ROOM_SCENE_PATTERN),
             player_runtime: PlayerRuntime::new(PLAYER_SCENE_PATH),
             boundary_detector: BoundaryDetector::new(TRANSITION_THRESHOLD),
             spawn_resolver: SpawnResolver::new(INITIAL_ROOM, INITIAL_PLAYER_POS),
-            room_session: RoomSession::new(INITIAL_ROOM),
+            room_session: RoomSession::new(INITIAL_ROOM, progress::global()),
         }
     }
 
     fn ready(&mut self) {
         godot_print!("[RoomManager] ready - initializing room transition system");
 
+        // Covers launching game.tscn directly from the editor, bypassing the main menu.
+        save::ensure_loaded();
+
         let initial_room = RoomId::from(self.initial_room);
         let initial_pos = self.initial_player_pos;
         self.spawn_resolver = SpawnResolver::new(initial_room, initial_pos);
-        self.room_session = RoomSession::new(initial_room);
+        self.room_session = RoomSession::new(initial_room, progress::global());
 
         let spawn = {
             let room_runtime = &mut self.room_runtime;
@@ -79,7 +87,9 @@ impl INode2D for GameRoomManager {
         }
     }
 
-    fn physics_process(&mut self, _delta: f64) {
+    fn physics_process(&mut self, delta: f64) {
+        save::add_playtime(delta);
+        save::add_room_time(self.room_session.current_room(), delta);
         self.player_runtime.tick_collision_restore();
         self.check_room_transitions();
     }
@@ -89,9 +99,11 @@ impl INode2D for GameRoomManager {
 impl GameRoomManager {
     fn finalize_room_load(&mut self, room: &Gd<Node2D>, room_id: RoomId) {
         self.room_session.complete_transition(room_id);
-        save::mark_room_explored(room_id);
         self.connect_portal_signals(room);
         godot_print!("[RoomManager] active room set to {:?}", room_id);
+        if self.autosave_on_room_load {
+            let _saved = save::autosave();
+        }
     }
 
     fn check_room_transitions(&mut self) {
@@ -144,6 +156,7 @@ impl GameRoomManager {
 
                 self.finalize_room_load(&new_room, plan.to_room);
                 self.room_runtime.set_current_room(new_room);
+                save::record_room_transition();
 
                 godot_print!(
                     "[RoomManager] room transition complete to {:?} at {:?}",
@@ -200,17 +213,22 @@ impl GameRoomManager {
     }
 
     #[func]
-    pub(crate) fn on_player_death_finished(&mut self) {
-        match self
-            .room_session
-            .plan_death(save::has_save(DEFAULT_SAVE_SLOT))
-        {
+    pub(crate) fn on_player_death_finished(&mut self, cause: GString) {
+        match DeathCause::from_name(&cause.to_string()) {
+            Some(cause) => save::record_death(self.room_session.current_room(), cause),
+            None => godot_warn!("[RoomManager] unknown death cause {}", cause),
+        }
+
+        let slot = save::active_slot();
+        match self.room_session.plan_death() {
             DeathPlan::ReloadCheckpoint => {
-                let _queued = save::queue_load(DEFAULT_SAVE_SLOT);
+                // Also drops transactional pickups made since the checkpoint
+                let _queued = save::queue_load(slot);
                 godot_print!("[RoomManager] player death - respawn at checkpoint");
             }
             DeathPlan::RestartGame => {
-                save::reset_all();
+                // Keeps this death, and the rest of the run's statistics
+                save::restart_run(slot);
                 godot_print!("[RoomManager] player death - restarting");
             }
         }
@@ -2,6 +2,7 @@ use godot::classes::CharacterBody2D;
 use godot::prelude::*;
 
 use crate::core::world::RoomId;
+use crate::entity::persistence;
 use crate::player::Player;
 use crate::rooms::{RoomLoadError, RoomLoader};
 
@@ -45,8 +46,12 @@ impl RoomRuntime {
         self.current_room_node = Some(room);
     }
 
+    /// Records where the room's crates came to rest before freeing it. Rooms
+    /// freed by a scene change skip this, so a restart cannot write the old
+    /// run's state into a reset slot.
     pub(crate) fn unload_current_room(&mut self, owner: &mut Gd<Node2D>) {
         if let Some(mut old_room) = self.current_room_node.take() {
+            persistence::save_runtime_state(&mut owner.get_tree());
             owner.remove_child(&old_room);
             old_room.queue_free();
         }
@@ -6,6 +6,7 @@ mod game;
 mod player;
 mod rooms;
 mod save;
+mod settings;
 mod ui;
 
 struct MyExtension;
@@ -16,6 +16,11 @@ pub struct AnimationNames {
     pub jump: &'static str,
     pub fall: &'static str,
     pub climb: &'static str,
+    pub wall_slide: &'static str,
+    pub dash: &'static str,
+    pub ledge_hang: &'static str,
+    pub crouch: &'static str,
+    pub crawl: &'static str,
 }
 
 impl Default for AnimationNames {
@@ -26,6 +31,15 @@ impl Default for AnimationNames {
             jump: "jump",
             fall: "fall",
             climb: "climb",
+            // The sprite sheet has no wall slide tag yet.
+            wall_slide: "fall",
+            // Nor a dash tag.
+            dash: "jump",
+            // Nor a ledge hang tag; a held climb frame reads closest.
+            ledge_hang: "climb",
+            // Nor crouch or crawl tags; the landing squash reads as a crouch.
+            crouch: "land",
+            crawl: "land",
         }
     }
 }
@@ -44,6 +58,16 @@ pub fn get_animation_name(
                 names.idle
             }
         }
+        MovementState::WallSlide => names.wall_slide,
+        MovementState::Dash => names.dash,
+        MovementState::LedgeHang => names.ledge_hang,
+        MovementState::Crouch => {
+            if is_walking {
+                names.crawl
+            } else {
+                names.crouch
+            }
+        }
         MovementState::Air => {
             if velocity.y > 0.0 {
                 names.fall
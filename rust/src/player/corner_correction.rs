@@ -1,9 +1,9 @@
 use godot::{classes::CharacterBody2D, prelude::*};
 
+use crate::core::player::is_wall_normal;
 use crate::core::world::{PLAYER_WIDTH, ROOM_WIDTH};
 
 const MAX_CORRECTION_PX: i32 = 3;
-const SIDE_NORMAL_THRESHOLD: f32 = 0.7;
 const INTENT_EPSILON: f32 = 0.01;
 const UPWARD_CLEARANCE_PX: f32 = -1.0;
 
@@ -28,6 +28,33 @@ pub fn apply_after_slide(
     );
 }
 
+/// Dashes get the upward correction above and also slip past ledge lips: a
+/// sideways dash that clips the top of a wall is lifted onto it.
+pub fn apply_after_dash(body: &mut Gd<CharacterBody2D>, attempted_velocity: Vector2) {
+    if attempted_velocity.y < 0.0 {
+        apply_after_slide(body, attempted_velocity, attempted_velocity.x);
+        return;
+    }
+    if attempted_velocity.x.abs() <= INTENT_EPSILON || body.get_collision_mask() == 0 {
+        return;
+    }
+    if !collision_context(body).has_side_collision {
+        return;
+    }
+
+    let forward_probe = Vector2::new(attempted_velocity.x.signum(), 0.0);
+    let transform = body.get_global_transform();
+    for px in 1..=MAX_CORRECTION_PX {
+        let offset = Vector2::new(0.0, -(px as f32));
+        if can_lift_over_ledge(body, transform, offset, forward_probe) {
+            let position = body.get_global_position();
+            godot_print!("[Player] dash ledge correction applied offset={:?}", offset);
+            body.set_global_position(position + offset);
+            return;
+        }
+    }
+}
+
 #[derive(Default)]
 struct CollisionContext {
     has_side_collision: bool,
@@ -36,6 +63,7 @@ struct CollisionContext {
 
 fn collision_context(body: &mut Gd<CharacterBody2D>) -> CollisionContext {
     let collision_count = body.get_slide_collision_count();
+    let floor_max_angle = body.get_floor_max_angle();
     let mut context = CollisionContext::default();
 
     for index in 0..collision_count {
@@ -44,7 +72,7 @@ fn collision_context(body: &mut Gd<CharacterBody2D>) -> CollisionContext {
         };
 
         let normal = collision.get_normal();
-        if normal.x.abs() < SIDE_NORMAL_THRESHOLD {
+        if !is_wall_normal(normal, floor_max_angle) {
             continue;
         }
 
@@ -92,18 +120,37 @@ fn can_apply_offset(
     correction_candidate_is_corner(false, upward_blocked, shifted_upward_blocked)
 }
 
+/// The corner rule with the axes swapped: room to rise, blocked ahead, and
+/// clear ahead once risen.
+fn can_lift_over_ledge(
+    body: &mut Gd<CharacterBody2D>,
+    transform: Transform2D,
+    offset: Vector2,
+    forward_probe: Vector2,
+) -> bool {
+    let lift_blocked = motion_collides(body, transform, offset);
+    if lift_blocked {
+        return false;
+    }
+
+    let forward_blocked = motion_collides(body, transform, forward_probe);
+    let lifted_forward_blocked = motion_collides(body, transform.translated(offset), forward_probe);
+
+    correction_candidate_is_corner(false, forward_blocked, lifted_forward_blocked)
+}
+
 /// Corner correction must keep the player inside the current room. The
 /// boundary passages are open holes in the walls, so a "clear" lateral probe
 /// next to one can report a valid-looking corner whose gap is actually the
 /// room exterior (no floor below). Nudging the player through such a gap
 /// drops them into unloaded space, so reject offsets that leave the room's
 /// horizontal interior.
-fn within_room_horizontal_bounds(x: f32) -> bool {
+pub(super) fn within_room_horizontal_bounds(x: f32) -> bool {
     let half_width = PLAYER_WIDTH * 0.5;
     x >= half_width && x <= ROOM_WIDTH - half_width
 }
 
-fn motion_collides(
+pub(super) fn motion_collides(
     body: &mut Gd<CharacterBody2D>,
     transform: Transform2D,
     motion: Vector2,
@@ -0,0 +1,50 @@
+//! Swaps the player between its standing and crouching collision shapes and
+//! checks for room to stand back up.
+
+use godot::{
+    classes::{CharacterBody2D, CollisionShape2D, RectangleShape2D},
+    prelude::*,
+};
+
+use super::corner_correction::motion_collides;
+
+pub struct CrouchShapes {
+    standing: Gd<CollisionShape2D>,
+    crouching: Gd<CollisionShape2D>,
+    /// Offset from the crouching shape's top edge to the standing one's,
+    /// taken from the scene so it follows the authored hitboxes.
+    stand_up_rise: Vector2,
+}
+
+impl CrouchShapes {
+    /// Both shapes must be rectangles; `None` otherwise.
+    pub fn new(
+        standing: Gd<CollisionShape2D>,
+        mut crouching: Gd<CollisionShape2D>,
+    ) -> Option<Self> {
+        let stand_up_rise = Vector2::new(0.0, top_edge(&standing)? - top_edge(&crouching)?);
+        crouching.set_disabled(true);
+        Some(Self {
+            standing,
+            crouching,
+            stand_up_rise,
+        })
+    }
+
+    pub fn set_crouching(&mut self, crouching: bool) {
+        self.standing.set_disabled(crouching);
+        self.crouching.set_disabled(!crouching);
+    }
+
+    /// Whether the standing hitbox would hit a ceiling; only meaningful while
+    /// the crouching shape is the active one.
+    pub fn ceiling_blocks_standing(&self, body: &mut Gd<CharacterBody2D>) -> bool {
+        let transform = body.get_global_transform();
+        motion_collides(body, transform, self.stand_up_rise)
+    }
+}
+
+fn top_edge(shape: &Gd<CollisionShape2D>) -> Option<f32> {
+    let rectangle = shape.get_shape()?.try_cast::<RectangleShape2D>().ok()?;
+    Some(shape.get_position().y - rectangle.get_size().y * 0.5)
+}
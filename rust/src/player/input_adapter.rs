@@ -6,6 +6,7 @@ use godot::prelude::*;
 
 use super::MovementInput;
 use super::aim_indicator::AimInput;
+use crate::core::settings::AccessibilitySettings;
 
 /// Input action names configuration.
 /// Allows customization of action names without changing logic.
@@ -17,8 +18,12 @@ pub struct InputActions {
     pub climb_up: &'static str,
     pub climb_down: &'static str,
     pub jump: &'static str,
+    pub dash: &'static str,
     pub drop_through: &'static str,
     pub respawn: &'static str,
+    /// Releasing jump early cuts the jump short.
+    pub cut_jump_on_release: bool,
+    pub respawn_enabled: bool,
 }
 
 impl Default for InputActions {
@@ -31,12 +36,22 @@ impl Default for InputActions {
             climb_up: "act_up",
             climb_down: "act_down",
             jump: "act_jump",
+            dash: "act_dash",
             drop_through: "act_down",
             respawn: "act_respawn",
+            cut_jump_on_release: true,
+            respawn_enabled: true,
         }
     }
 }
 
+impl InputActions {
+    pub fn apply_accessibility(&mut self, accessibility: &AccessibilitySettings) {
+        self.cut_jump_on_release = !accessibility.full_jump_on_tap;
+        self.respawn_enabled = !accessibility.disable_quick_respawn;
+    }
+}
+
 /// Collect movement input from Godot Input singleton.
 pub fn collect_movement_input(actions: &InputActions) -> MovementInput {
     let input = Input::singleton();
@@ -44,7 +59,21 @@ pub fn collect_movement_input(actions: &InputActions) -> MovementInput {
         direction: input.get_axis(actions.walk_left, actions.walk_right),
         vertical_direction: input.get_axis(actions.climb_up, actions.climb_down),
         jump_just_pressed: input.is_action_just_pressed(actions.jump),
-        jump_just_released: input.is_action_just_released(actions.jump),
+        jump_just_released: actions.cut_jump_on_release
+            && input.is_action_just_released(actions.jump),
+        // Taps count as held when jumps are never cut, so they reach the
+        // same apex.
+        jump_held: !actions.cut_jump_on_release || input.is_action_pressed(actions.jump),
+        // Filled in by the player from its body's wall contact.
+        wall_direction: 0.0,
+        dash_just_pressed: input.is_action_just_pressed(actions.dash),
+        // Zero without input; the player then dashes the way it faces.
+        dash_direction: input.get_vector(
+            actions.walk_left,
+            actions.walk_right,
+            actions.aim_up,
+            actions.aim_down,
+        ),
     }
 }
 
@@ -64,7 +93,7 @@ pub fn is_drop_through_pressed(actions: &InputActions) -> bool {
 
 /// Check if quick respawn action was just pressed.
 pub fn is_respawn_pressed(actions: &InputActions) -> bool {
-    Input::singleton().is_action_just_pressed(actions.respawn)
+    actions.respawn_enabled && Input::singleton().is_action_just_pressed(actions.respawn)
 }
 
 /// Get horizontal push direction for rigid body pushing.
@@ -83,4 +112,21 @@ mod tests {
     fn default_actions_include_respawn_action() {
         assert_eq!(InputActions::default().respawn, "act_respawn");
     }
+
+    #[test]
+    fn default_actions_include_dash_action() {
+        assert_eq!(InputActions::default().dash, "act_dash");
+    }
+
+    #[test]
+    fn accessibility_settings_toggle_jump_cut_and_respawn() {
+        let mut actions = InputActions::default();
+        actions.apply_accessibility(&AccessibilitySettings {
+            full_jump_on_tap: true,
+            disable_quick_respawn: true,
+        });
+
+        assert!(!actions.cut_jump_on_release);
+        assert!(!actions.respawn_enabled);
+    }
 }
@@ -0,0 +1,95 @@
+//! Finds ledge corners the player can hang from, probing with whole-body
+//! test moves the same way corner correction does.
+
+use godot::{
+    classes::{CharacterBody2D, KinematicCollision2D},
+    prelude::*,
+};
+
+use crate::core::world::{PLAYER_HEIGHT, PLAYER_WIDTH};
+
+use super::corner_correction::{motion_collides, within_room_horizontal_bounds};
+use super::hazard;
+use super::{HAZARD_LAYER, HAZARD_TILEMAP_PREFIXES};
+
+const SIDE_EPSILON: f32 = 0.01;
+/// How far below the top of the body a ledge corner can sit and still be
+/// grabbed. `PLAYER_HEIGHT` bounds the body's shape.
+const GRAB_WINDOW_PX: i32 = 8;
+const GROUND_PROBE_PX: f32 = 2.0;
+
+pub struct LedgeGrab {
+    /// -1.0 for a ledge on the left, 1.0 on the right.
+    pub side: f32,
+    /// Moves the hanging body up and forward to stand on the ledge.
+    pub mantle_offset: Vector2,
+}
+
+/// A ledge is a wall beside the body whose top lies level with the body's top,
+/// with room to climb up and stand on it. Hazards never count: neither the
+/// wall nor the ground on top of it may hurt.
+pub fn find_ledge(body: &mut Gd<CharacterBody2D>, side: f32) -> Option<LedgeGrab> {
+    if side.abs() < SIDE_EPSILON || body.get_collision_mask() == 0 {
+        return None;
+    }
+
+    let side = side.signum();
+    let forward = Vector2::new(side, 0.0);
+    let transform = body.get_global_transform();
+    let wall = motion_collision(body, transform, forward)?;
+    if is_hazard(&wall) {
+        return None;
+    }
+
+    let lowest_lift = PLAYER_HEIGHT as i32 - GRAB_WINDOW_PX;
+    let lifted = |lift: i32| transform.translated(Vector2::new(0.0, -(lift as f32)));
+    if !motion_collides(body, lifted(lowest_lift), forward) {
+        return None;
+    }
+    let lift = (lowest_lift + 1..=PLAYER_HEIGHT as i32)
+        .find(|lift| !motion_collides(body, lifted(*lift), forward))?;
+
+    let rise = Vector2::new(0.0, -(lift as f32));
+    let step = Vector2::new(side * PLAYER_WIDTH, 0.0);
+    if motion_collides(body, transform, rise) || motion_collides(body, lifted(lift), step) {
+        return None;
+    }
+
+    let mantle_offset = rise + step;
+    let destination = transform.translated(mantle_offset);
+    if !within_room_horizontal_bounds(destination.origin.x) {
+        return None;
+    }
+    let ground = motion_collision(body, destination, Vector2::new(0.0, GROUND_PROBE_PX))?;
+    if is_hazard(&ground) {
+        return None;
+    }
+
+    Some(LedgeGrab {
+        side,
+        mantle_offset,
+    })
+}
+
+fn motion_collision(
+    body: &mut Gd<CharacterBody2D>,
+    transform: Transform2D,
+    motion: Vector2,
+) -> Option<Gd<KinematicCollision2D>> {
+    let collision = KinematicCollision2D::new_gd();
+    // gdext does not expose CharacterBody2D::test_move as a static Rust method.
+    body.call(
+        "test_move",
+        &[
+            transform.to_variant(),
+            motion.to_variant(),
+            collision.to_variant(),
+        ],
+    )
+    .to::<bool>()
+    .then_some(collision)
+}
+
+fn is_hazard(collision: &Gd<KinematicCollision2D>) -> bool {
+    hazard::is_hazard_collision(collision, HAZARD_LAYER, &HAZARD_TILEMAP_PREFIXES)
+}
@@ -1,27 +1,41 @@
 mod aim_indicator;
 mod animation;
 mod corner_correction;
+mod crouch;
 mod hazard;
 mod input_adapter;
 mod ladder;
+mod ledge;
+mod movement_profile;
 mod platform;
 mod push;
 pub(crate) mod water;
 
-pub use crate::core::player::{MovementConfig, MovementInput, MovementState, PlayerMovement};
+pub use crate::core::player::{
+    MovementAbilities, MovementConfig, MovementInput, MovementState, PlayerMovement,
+};
 pub use animation::AnimationNames;
 pub use input_adapter::InputActions;
 
 use godot::{
     classes::{
-        AnimatedSprite2D, CharacterBody2D, ICharacterBody2D, Node2D, Polygon2D, ProjectSettings,
+        AnimatedSprite2D, CharacterBody2D, CollisionShape2D, ICharacterBody2D, Node2D, Polygon2D,
+        ProjectSettings, character_body_2d::PlatformOnLeave,
     },
     prelude::*,
 };
 
+use crate::core::player::jump_reach::{self, JumpReach};
+use crate::core::progress::abilities::{Ability, AbilitySet};
+use crate::core::progress::statistics::DeathCause;
 use crate::entity::water_zone::{WATER_ZONE_GROUP, WaterZone};
+use crate::save;
+use crate::settings;
 
 use self::aim_indicator::{AimDirection, AimIndicator, AimInput};
+use self::crouch::CrouchShapes;
+use self::ledge::LedgeGrab;
+use self::movement_profile::ProfileWatcher;
 use self::platform::PlatformDropController;
 
 const MOVING_PLATFORM_LAYER: i32 = 4;
@@ -32,6 +46,8 @@ const DEATH_ANIMATION: &str = "death";
 const HAZARD_TILEMAP_PREFIXES: [&str; 2] = ["HazardsTiles", "Hazards"];
 const WATER_BODY_OVERLAY_PATH: &str = "WaterBodyOverlay";
 const WATER_SURFACE_OVERLAY_PATH: &str = "WaterSurfaceOverlay";
+const STANDING_SHAPE_PATH: &str = "CollisionShape2D";
+const CROUCHING_SHAPE_PATH: &str = "CrouchCollisionShape2D";
 const PLAYER_HALF_WIDTH_PX: f32 = 8.0;
 const WATER_SURFACE_OVERLAY_HEIGHT_PX: f32 = 1.0;
 
@@ -44,6 +60,10 @@ pub struct Player {
     aim_indicator: Option<Gd<AimIndicator>>,
     water_body_overlay: Option<Gd<Polygon2D>>,
     water_surface_overlay: Option<Gd<Polygon2D>>,
+    crouch_shapes: Option<CrouchShapes>,
+    /// `MovementProfile` resource; empty uses the built-in defaults.
+    #[export(file = "*.tres")]
+    movement_profile: GString,
     #[export]
     aim_indicator_distance: f32,
     #[export]
@@ -65,10 +85,12 @@ pub struct Player {
     drop_controller: PlatformDropController,
     aim_direction: AimDirection,
     is_dying: bool,
+    death_cause: DeathCause,
     is_climbing: bool,
     ladder_regrab_blocked: bool,
     water_state: water::WaterState,
     last_water_zone: Option<Gd<WaterZone>>,
+    profile_watcher: Option<ProfileWatcher>,
 }
 
 struct PlayerWaterContact {
@@ -127,6 +149,8 @@ impl ICharacterBody2D for Player {
             aim_indicator: None,
             water_body_overlay: None,
             water_surface_overlay: None,
+            crouch_shapes: None,
+            movement_profile: GString::from(movement_profile::DEFAULT_PROFILE_PATH),
             aim_indicator_distance: 12.0,
             water_surface_snap_depth: water_tuning.surface_snap_depth,
             water_surface_float_depth: water_tuning.surface_float_depth,
@@ -145,17 +169,22 @@ impl ICharacterBody2D for Player {
             ),
             aim_direction: AimDirection::default(),
             is_dying: false,
+            death_cause: DeathCause::Hazard,
             is_climbing: false,
             ladder_regrab_blocked: false,
             water_state: water::WaterState::default(),
             last_water_zone: None,
+            profile_watcher: None,
         }
     }
 
     fn ready(&mut self) {
-        self.movement = Some(PlayerMovement::new(MovementConfig::platformer(
-            project_gravity(),
-        )));
+        let profile_path = self.movement_profile.to_string();
+        let config = movement_profile::load(&profile_path, default_movement_config());
+        let mut body = self.to_gd().upcast::<CharacterBody2D>();
+        configure_floor(&mut body, &config);
+        self.movement = Some(PlayerMovement::new(config));
+        self.profile_watcher = ProfileWatcher::new(&profile_path);
 
         let moving_platform_mask_default =
             self.base().get_collision_mask_value(MOVING_PLATFORM_LAYER);
@@ -167,6 +196,13 @@ impl ICharacterBody2D for Player {
             .signals()
             .animation_finished()
             .connect_other(&player, Self::on_animation_finished);
+        self.on_settings_changed();
+        if let Some(settings) = settings::find_settings(&player.clone().upcast::<Node>()) {
+            settings
+                .signals()
+                .changed()
+                .connect_other(&player, Self::on_settings_changed);
+        }
         if let Some(mut frames) = self.sprite.get_sprite_frames() {
             frames.set_animation_loop(DEATH_ANIMATION, false);
         }
@@ -192,10 +228,27 @@ impl ICharacterBody2D for Player {
         }
         self.hide_water_overlay();
 
+        let standing_shape = self
+            .base()
+            .try_get_node_as::<CollisionShape2D>(STANDING_SHAPE_PATH);
+        let crouching_shape = self
+            .base()
+            .try_get_node_as::<CollisionShape2D>(CROUCHING_SHAPE_PATH);
+        self.crouch_shapes = standing_shape
+            .zip(crouching_shape)
+            .and_then(|(standing, crouching)| CrouchShapes::new(standing, crouching));
+        if self.crouch_shapes.is_none() {
+            godot_warn!(
+                "[Player] crouch collision shape not found - add a rectangular CrouchCollisionShape2D beside CollisionShape2D to enable crouching"
+            );
+        }
+
         godot_print!("[Player] ready")
     }
 
     fn physics_process(&mut self, delta: f64) {
+        self.reload_movement_profile(delta);
+
         if self.is_dying {
             self.base_mut().set_velocity(Vector2::ZERO);
             self.set_aim_indicator_visible(false);
@@ -204,12 +257,18 @@ impl ICharacterBody2D for Player {
         }
 
         if input_adapter::is_respawn_pressed(&self.input_actions) {
-            self.start_death();
+            self.start_death(DeathCause::Respawn);
             return;
         }
 
-        let movement_input = input_adapter::collect_movement_input(&self.input_actions);
+        let mut movement_input = input_adapter::collect_movement_input(&self.input_actions);
+        movement_input.wall_direction = self.wall_contact_direction();
+        movement_input.floor_normal = self.floor_normal();
+        if movement_input.dash_direction.is_zero_approx() {
+            movement_input.dash_direction = self.aim_direction.offset(1.0);
+        }
         let mut body = self.to_gd().upcast::<CharacterBody2D>();
+        movement_input.platform_velocity = platform::standing_platform_velocity(&mut body);
         let touching_ladder = self.is_touching_ladder();
         self.update_ladder_regrab_block(movement_input, touching_ladder);
         let mut jumped_from_ladder = false;
@@ -252,10 +311,27 @@ impl ICharacterBody2D for Player {
         if self.drop_controller.is_active() {
             is_on_floor = false;
         }
-
+        movement_input.can_crouch = self.crouch_shapes.is_some()
+            && !self.drop_controller.is_standing_on_platform(&mut body);
+        let crouched = self
+            .movement
+            .as_ref()
+            .is_some_and(PlayerMovement::is_crouching);
+        movement_input.ceiling_blocks_standing = crouched
+            && self
+                .crouch_shapes
+                .as_ref()
+                .is_some_and(|shapes| shapes.ceiling_blocks_standing(&mut body));
+
+        // Read every frame so a pickup takes effect at once and a slot load
+        // can never leave stale unlocks behind.
+        let unlocked = save::unlocked_abilities();
         let water_tuning = self.water_tuning();
         let resolved_water = self.resolve_water_contact(velocity, water_tuning.surface_snap_depth);
         let water_contact = resolved_water.contact;
+        if water_contact != water::WaterContact::None && !unlocked.contains(Ability::SwimDive) {
+            movement_input = water::input_without_dive(movement_input);
+        }
         let player_position_for_water_event = body.get_global_position();
         let water_events = self.water_state.update_and_events(
             water_contact,
@@ -289,10 +365,20 @@ impl ICharacterBody2D for Player {
             self.snap_to_water_surface_float(&mut body, surface_y, water_tuning);
         }
 
+        let ledge = if water_contact == water::WaterContact::None {
+            self.find_ledge(&mut body, velocity, is_on_floor, movement_input.direction)
+        } else {
+            None
+        };
+        movement_input.ledge_direction = ledge.as_ref().map_or(0.0, |ledge| ledge.side);
+
         let movement_velocity = water::velocity_for_surface(velocity, water_contact);
         let movement_input_for_physics =
             if water_contact.is_surface() || water_contact.is_submerged() {
-                water::input_without_regular_jump(movement_input)
+                MovementInput {
+                    dash_just_pressed: false,
+                    ..water::input_without_regular_jump(movement_input)
+                }
             } else {
                 movement_input
             };
@@ -301,12 +387,24 @@ impl ICharacterBody2D for Player {
         let Some(movement) = self.movement.as_mut() else {
             return;
         };
+        movement.abilities = movement_abilities(unlocked);
         let mut new_velocity = movement.physics_process(
             movement_velocity,
             is_on_floor || jumped_from_ladder,
             delta,
             movement_input_for_physics,
         );
+        if movement.jumped_this_frame() {
+            save::record_jump();
+        }
+        if movement.mantled_this_frame()
+            && let Some(ledge) = &ledge
+        {
+            let position = body.get_global_position();
+            body.set_global_position(position + ledge.mantle_offset);
+        }
+        let is_dashing = movement.is_dashing();
+        let is_crouching = movement.is_crouching();
         if water_contact.is_surface() {
             new_velocity = water::velocity_for_surface_float(
                 new_velocity,
@@ -319,10 +417,19 @@ impl ICharacterBody2D for Player {
                 water::velocity_for_submerged(new_velocity, movement_input, water_tuning);
         }
 
+        self.set_crouching(is_crouching);
         self.base_mut().set_velocity(new_velocity);
         self.base_mut().move_and_slide();
         if water_contact == water::WaterContact::None {
-            corner_correction::apply_after_slide(&mut body, new_velocity, movement_input.direction);
+            if is_dashing {
+                corner_correction::apply_after_dash(&mut body, new_velocity);
+            } else {
+                corner_correction::apply_after_slide(
+                    &mut body,
+                    new_velocity,
+                    movement_input.direction,
+                );
+            }
         }
         self.update_water_overlay(water_contact, body.get_global_position());
 
@@ -340,7 +447,7 @@ impl ICharacterBody2D for Player {
         };
 
         if self.check_hazard_collision() {
-            self.start_death();
+            self.start_death(DeathCause::Hazard);
             return;
         }
 
@@ -360,7 +467,24 @@ impl ICharacterBody2D for Player {
             is_walking,
             &self.animation_names,
         );
-        animation::play_animation_if_changed(&mut self.sprite, anim);
+        if state == MovementState::LedgeHang {
+            animation::set_animation_paused(&mut self.sprite, anim, true);
+        } else {
+            animation::play_animation_if_changed(&mut self.sprite, anim);
+        }
+    }
+}
+
+fn default_movement_config() -> MovementConfig {
+    MovementConfig::platformer(project_gravity())
+}
+
+/// Maps the slot's unlocks onto the movement behaviours they enable.
+fn movement_abilities(unlocked: AbilitySet) -> MovementAbilities {
+    MovementAbilities {
+        double_jump: unlocked.contains(Ability::DoubleJump),
+        dash: unlocked.contains(Ability::Dash),
+        wall_jump: unlocked.contains(Ability::WallJump),
     }
 }
 
@@ -369,6 +493,18 @@ fn project_gravity() -> f32 {
     settings.get("physics/2d/default_gravity").to::<f64>() as f32
 }
 
+/// Slopes up to the configured angle are floor; constant speed keeps the
+/// walk speed along them and stop-on-slope keeps an idle player in place.
+fn configure_floor(body: &mut Gd<CharacterBody2D>, config: &MovementConfig) {
+    body.set_floor_max_angle(config.floor_max_angle());
+    body.set_floor_snap_length(config.floor_snap_length);
+    body.set_floor_constant_speed_enabled(true);
+    body.set_floor_stop_on_slope_enabled(true);
+    // `PlayerMovement` carries platform velocity itself, capped, so the body
+    // must not add it again on leaving.
+    body.set_platform_on_leave(PlatformOnLeave::DO_NOTHING);
+}
+
 fn set_polygon_rect(polygon: &mut Gd<Polygon2D>, left: f32, right: f32, top: f32, bottom: f32) {
     polygon.set_polygon(&PackedVector2Array::from_iter([
         Vector2::new(left, top),
@@ -380,8 +516,9 @@ fn set_polygon_rect(polygon: &mut Gd<Polygon2D>, left: f32, right: f32, top: f32
 
 #[godot_api]
 impl Player {
+    /// `cause` is a `DeathCause` name.
     #[signal]
-    pub(crate) fn death_finished();
+    pub(crate) fn death_finished(cause: GString);
 
     #[func]
     fn on_animation_finished(&mut self) {
@@ -391,7 +528,35 @@ impl Player {
 
         if self.sprite.get_animation() == DEATH_ANIMATION {
             self.is_dying = false;
-            self.signals().death_finished().emit();
+            let cause = GString::from(self.death_cause.name());
+            self.signals().death_finished().emit(&cause);
+        }
+    }
+
+    #[func]
+    fn on_settings_changed(&mut self) {
+        self.input_actions
+            .apply_accessibility(&settings::current().accessibility);
+    }
+
+    /// One reachability number of the current movement tuning, e.g.
+    /// "max_height" or "coyote_jump_distance"; unknown metrics are zero, and
+    /// every metric is NaN when the tuning cannot jump at all.
+    #[func]
+    fn get_jump_reach(&self, metric: GString) -> f64 {
+        match self.jump_reach() {
+            Some(reach) => reach.metric(&metric.to_string()).map_or(0.0, f64::from),
+            None => f64::NAN,
+        }
+    }
+
+    /// All reachability numbers as a table, as printed by
+    /// `cargo xtask jump-reach`.
+    #[func]
+    fn get_jump_reach_report(&self) -> GString {
+        match self.jump_reach() {
+            Some(reach) => GString::from(&reach.to_string()),
+            None => GString::from("unreachable: this movement tuning cannot start a jump"),
         }
     }
 
@@ -400,6 +565,7 @@ impl Player {
             movement.reset_transient_state();
         }
 
+        self.set_crouching(false);
         self.is_climbing = false;
         self.ladder_regrab_blocked = false;
         self.water_state.update(water::WaterContact::None);
@@ -411,6 +577,30 @@ impl Player {
         self.set_aim_indicator_visible(false);
     }
 
+    fn jump_reach(&self) -> Option<JumpReach> {
+        match &self.movement {
+            Some(movement) => jump_reach::jump_reach(&movement.config),
+            None => jump_reach::jump_reach(&default_movement_config()),
+        }
+    }
+
+    /// Applies profile edits in place, keeping timers and charges, so a
+    /// designer can tune mid-jump.
+    fn reload_movement_profile(&mut self, delta: f64) {
+        let Some(watcher) = self.profile_watcher.as_mut() else {
+            return;
+        };
+        let Some(config) = watcher.poll(delta, project_gravity()) else {
+            return;
+        };
+
+        let mut body = self.to_gd().upcast::<CharacterBody2D>();
+        configure_floor(&mut body, &config);
+        if let Some(movement) = &mut self.movement {
+            movement.config = config;
+        }
+    }
+
     fn water_tuning(&self) -> water::WaterTuning {
         water_tuning_from_exports(
             self.water_surface_snap_depth,
@@ -477,6 +667,7 @@ impl Player {
         if let Some(movement) = &mut self.movement {
             movement.reset_transient_state();
         }
+        self.set_crouching(false);
     }
 
     fn stop_climbing(&mut self) {
@@ -498,7 +689,7 @@ impl Player {
         self.base_mut().move_and_slide();
 
         if self.check_hazard_collision() {
-            self.start_death();
+            self.start_death(DeathCause::Hazard);
             return;
         }
 
@@ -514,6 +705,47 @@ impl Player {
         }
     }
 
+    /// Side of the wall the last slide pushed into while airborne, as
+    /// `MovementInput::wall_direction` expects.
+    fn wall_contact_direction(&self) -> f32 {
+        if !self.base().is_on_wall_only() {
+            return 0.0;
+        }
+        -self.base().get_wall_normal().x.signum()
+    }
+
+    fn floor_normal(&self) -> Vector2 {
+        if self.base().is_on_floor() {
+            self.base().get_floor_normal()
+        } else {
+            Vector2::ZERO
+        }
+    }
+
+    /// Probes the side being hung from, otherwise the held direction while
+    /// falling.
+    fn find_ledge(
+        &self,
+        body: &mut Gd<CharacterBody2D>,
+        velocity: Vector2,
+        is_on_floor: bool,
+        direction: f32,
+    ) -> Option<LedgeGrab> {
+        let hanging_side = self.movement.as_ref().and_then(PlayerMovement::ledge_side);
+        let side = match hanging_side {
+            Some(side) => side,
+            None if !is_on_floor && velocity.y >= 0.0 => direction,
+            None => return None,
+        };
+        ledge::find_ledge(body, side)
+    }
+
+    fn set_crouching(&mut self, crouching: bool) {
+        if let Some(shapes) = self.crouch_shapes.as_mut() {
+            shapes.set_crouching(crouching);
+        }
+    }
+
     fn is_touching_ladder(&self) -> bool {
         let player = self.to_gd().upcast::<Node2D>();
         ladder::is_touching_ladder(&player)
@@ -575,10 +807,11 @@ impl Player {
         body.set_global_position(position);
     }
 
-    fn start_death(&mut self) {
+    fn start_death(&mut self, cause: DeathCause) {
         if self.is_dying {
             return;
         }
+        self.death_cause = cause;
         self.is_climbing = false;
         self.ladder_regrab_blocked = false;
         self.water_state.update(water::WaterContact::None);
@@ -0,0 +1,271 @@
+//! Loads the player's `MovementConfig` from a `MovementProfile` resource
+//! under `res://` and, in debug builds, reloads it when the file changes.
+
+use godot::classes::resource_loader::CacheMode;
+use godot::classes::{FileAccess, IResource, Os, Resource, ResourceLoader};
+use godot::prelude::*;
+
+use crate::core::player::profile;
+
+use super::MovementConfig;
+
+pub const DEFAULT_PROFILE_PATH: &str = "res://player/movement.tres";
+const POLL_INTERVAL_SECONDS: f64 = 0.5;
+
+/// Movement tuning edited in the inspector; each field mirrors the
+/// `MovementConfig` field of the same name. A new profile starts from the
+/// built-in defaults, so a `.tres` only stores the values a designer changed.
+#[derive(GodotClass)]
+#[class(base=Resource)]
+pub struct MovementProfile {
+    base: Base<Resource>,
+    /// 0.0 uses the project's `physics/2d/default_gravity`.
+    #[export]
+    gravity: f32,
+    #[export]
+    walk_speed: f32,
+    #[export]
+    ground_accel_speed: f32,
+    #[export]
+    ground_decel_speed: f32,
+    #[export]
+    air_accel_speed: f32,
+    #[export]
+    air_decel_speed: f32,
+    #[export]
+    turn_accel_multiplier: f32,
+    #[export]
+    jump_velocity: f32,
+    #[export]
+    jump_buffer_time: f32,
+    #[export]
+    coyote_time: f32,
+    #[export]
+    air_jumps: u32,
+    #[export]
+    jump_release_velocity_factor: f32,
+    #[export]
+    apex_gravity_multiplier: f32,
+    #[export]
+    apex_velocity_threshold: f32,
+    #[export]
+    fall_gravity_multiplier: f32,
+    #[export]
+    max_fall_speed: f32,
+    #[export]
+    fast_fall_gravity_multiplier: f32,
+    #[export]
+    fast_fall_max_speed: f32,
+    #[export]
+    min_walk_speed: f32,
+    #[export]
+    floor_max_angle_degrees: f32,
+    #[export]
+    floor_snap_length: f32,
+    #[export]
+    slope_jump_normal_blend: f32,
+    #[export]
+    max_inherited_platform_speed: f32,
+    #[export]
+    climb_speed: f32,
+    #[export]
+    wall_slide_max_speed: f32,
+    #[export]
+    wall_jump_kick_speed: f32,
+    #[export]
+    wall_jump_lockout_time: f32,
+    #[export]
+    wall_coyote_time: f32,
+    #[export]
+    ledge_regrab_time: f32,
+    #[export]
+    crouch_speed_multiplier: f32,
+    #[export]
+    dash_speed: f32,
+    #[export]
+    dash_duration: f32,
+    #[export]
+    dash_cooldown: f32,
+    #[export]
+    dash_buffer_time: f32,
+    #[export]
+    air_dash_charges: u32,
+}
+
+#[godot_api]
+impl IResource for MovementProfile {
+    fn init(base: Base<Resource>) -> Self {
+        let config = MovementConfig::platformer(0.0);
+        Self {
+            base,
+            gravity: config.gravity,
+            walk_speed: config.walk_speed,
+            ground_accel_speed: config.ground_accel_speed,
+            ground_decel_speed: config.ground_decel_speed,
+            air_accel_speed: config.air_accel_speed,
+            air_decel_speed: config.air_decel_speed,
+            turn_accel_multiplier: config.turn_accel_multiplier,
+            jump_velocity: config.jump_velocity,
+            jump_buffer_time: config.jump_buffer_time,
+            coyote_time: config.coyote_time,
+            air_jumps: config.air_jumps,
+            jump_release_velocity_factor: config.jump_release_velocity_factor,
+            apex_gravity_multiplier: config.apex_gravity_multiplier,
+            apex_velocity_threshold: config.apex_velocity_threshold,
+            fall_gravity_multiplier: config.fall_gravity_multiplier,
+            max_fall_speed: config.max_fall_speed,
+            fast_fall_gravity_multiplier: config.fast_fall_gravity_multiplier,
+            fast_fall_max_speed: config.fast_fall_max_speed,
+            min_walk_speed: config.min_walk_speed,
+            floor_max_angle_degrees: config.floor_max_angle_degrees,
+            floor_snap_length: config.floor_snap_length,
+            slope_jump_normal_blend: config.slope_jump_normal_blend,
+            max_inherited_platform_speed: config.max_inherited_platform_speed,
+            climb_speed: config.climb_speed,
+            wall_slide_max_speed: config.wall_slide_max_speed,
+            wall_jump_kick_speed: config.wall_jump_kick_speed,
+            wall_jump_lockout_time: config.wall_jump_lockout_time,
+            wall_coyote_time: config.wall_coyote_time,
+            ledge_regrab_time: config.ledge_regrab_time,
+            crouch_speed_multiplier: config.crouch_speed_multiplier,
+            dash_speed: config.dash_speed,
+            dash_duration: config.dash_duration,
+            dash_cooldown: config.dash_cooldown,
+            dash_buffer_time: config.dash_buffer_time,
+            air_dash_charges: config.air_dash_charges,
+        }
+    }
+}
+
+impl MovementProfile {
+    fn to_config(&self, default_gravity: f32) -> MovementConfig {
+        MovementConfig {
+            gravity: if self.gravity == 0.0 {
+                default_gravity
+            } else {
+                self.gravity
+            },
+            walk_speed: self.walk_speed,
+            ground_accel_speed: self.ground_accel_speed,
+            ground_decel_speed: self.ground_decel_speed,
+            air_accel_speed: self.air_accel_speed,
+            air_decel_speed: self.air_decel_speed,
+            turn_accel_multiplier: self.turn_accel_multiplier,
+            jump_velocity: self.jump_velocity,
+            jump_buffer_time: self.jump_buffer_time,
+            coyote_time: self.coyote_time,
+            air_jumps: self.air_jumps,
+            jump_release_velocity_factor: self.jump_release_velocity_factor,
+            apex_gravity_multiplier: self.apex_gravity_multiplier,
+            apex_velocity_threshold: self.apex_velocity_threshold,
+            fall_gravity_multiplier: self.fall_gravity_multiplier,
+            max_fall_speed: self.max_fall_speed,
+            fast_fall_gravity_multiplier: self.fast_fall_gravity_multiplier,
+            fast_fall_max_speed: self.fast_fall_max_speed,
+            min_walk_speed: self.min_walk_speed,
+            floor_max_angle_degrees: self.floor_max_angle_degrees,
+            floor_snap_length: self.floor_snap_length,
+            slope_jump_normal_blend: self.slope_jump_normal_blend,
+            max_inherited_platform_speed: self.max_inherited_platform_speed,
+            climb_speed: self.climb_speed,
+            wall_slide_max_speed: self.wall_slide_max_speed,
+            wall_jump_kick_speed: self.wall_jump_kick_speed,
+            wall_jump_lockout_time: self.wall_jump_lockout_time,
+            wall_coyote_time: self.wall_coyote_time,
+            ledge_regrab_time: self.ledge_regrab_time,
+            crouch_speed_multiplier: self.crouch_speed_multiplier,
+            dash_speed: self.dash_speed,
+            dash_duration: self.dash_duration,
+            dash_cooldown: self.dash_cooldown,
+            dash_buffer_time: self.dash_buffer_time,
+            air_dash_charges: self.air_dash_charges,
+        }
+    }
+}
+
+/// Reads the profile, taking gravity from `defaults` when it leaves gravity
+/// at 0.0; an empty path, or a missing or invalid profile, leaves the
+/// defaults in place.
+pub fn load(path: &str, defaults: MovementConfig) -> MovementConfig {
+    if path.is_empty() {
+        return defaults;
+    }
+
+    match read(path, defaults.gravity) {
+        Ok(config) => {
+            godot_print!("[Player] loaded movement profile {}", path);
+            config
+        }
+        Err(message) => {
+            godot_error!("[Player] {} - using default movement", message);
+            defaults
+        }
+    }
+}
+
+fn read(path: &str, default_gravity: f32) -> Result<MovementConfig, String> {
+    let mut loader = ResourceLoader::singleton();
+    if !loader.exists(path) {
+        return Err(format!("movement profile {path} not found"));
+    }
+
+    // Skips the cache so a reload sees the edited file.
+    let movement = loader
+        .load_ex(path)
+        .cache_mode(CacheMode::IGNORE)
+        .done()
+        .and_then(|resource| resource.try_cast::<MovementProfile>().ok())
+        .ok_or_else(|| format!("{path} is not a MovementProfile"))?;
+    let config = movement.bind().to_config(default_gravity);
+    profile::validate(&config).map_err(|error| format!("movement profile {path}: {error}"))?;
+    Ok(config)
+}
+
+/// Polls the profile's modification time so designers see edits without
+/// restarting; only debug builds watch.
+pub struct ProfileWatcher {
+    path: String,
+    modified_time: u64,
+    poll_timer: f64,
+}
+
+impl ProfileWatcher {
+    pub fn new(path: &str) -> Option<Self> {
+        if !Os::singleton().is_debug_build() || !FileAccess::file_exists(path) {
+            return None;
+        }
+
+        Some(Self {
+            path: path.to_string(),
+            modified_time: FileAccess::get_modified_time(path),
+            poll_timer: POLL_INTERVAL_SECONDS,
+        })
+    }
+
+    /// The reloaded config when the file changed and still loads. A broken
+    /// edit is reported once and the current movement kept.
+    pub fn poll(&mut self, delta: f64, default_gravity: f32) -> Option<MovementConfig> {
+        self.poll_timer -= delta;
+        if self.poll_timer > 0.0 {
+            return None;
+        }
+        self.poll_timer = POLL_INTERVAL_SECONDS;
+
+        let modified_time = FileAccess::get_modified_time(self.path.as_str());
+        if modified_time == self.modified_time {
+            return None;
+        }
+        self.modified_time = modified_time;
+
+        match read(&self.path, default_gravity) {
+            Ok(config) => {
+                godot_print!("[Player] reloaded movement profile {}", self.path);
+                Some(config)
+            }
+            Err(message) => {
+                godot_warn!("[Player] {} - keeping current movement", message);
+                None
+            }
+        }
+    }
+}
@@ -3,6 +3,9 @@ use godot::{
     prelude::*,
 };
 
+use crate::core::player::is_floor_normal;
+use crate::entity::moving_platform::MovingPlatform;
+
 pub struct PlatformDropController {
     timer: f64,
     duration: f64,
@@ -51,6 +54,11 @@ impl PlatformDropController {
         self.timer > 0.0
     }
 
+    /// Whether the floor is a platform this controller can drop through.
+    pub fn is_standing_on_platform(&self, body: &mut Gd<CharacterBody2D>) -> bool {
+        is_standing_on_platform(body, self.collision_layer)
+    }
+
     pub fn reset(&mut self, body: &mut Gd<CharacterBody2D>) {
         if self.timer > 0.0 {
             self.stop(body);
@@ -68,6 +76,31 @@ impl PlatformDropController {
     }
 }
 
+/// Velocity of the `MovingPlatform` under the body; zero on other ground or
+/// in the air.
+pub fn standing_platform_velocity(body: &mut Gd<CharacterBody2D>) -> Vector2 {
+    if !body.is_on_floor() {
+        return Vector2::ZERO;
+    }
+
+    let floor_max_angle = body.get_floor_max_angle();
+    for index in 0..body.get_slide_collision_count() {
+        let Some(collision) = body.get_slide_collision(index) else {
+            continue;
+        };
+        if !is_floor_normal(collision.get_normal(), floor_max_angle) {
+            continue;
+        }
+        let Some(collider) = collision.get_collider() else {
+            continue;
+        };
+        if let Ok(platform) = collider.try_cast::<MovingPlatform>() {
+            return platform.bind().get_platform_velocity();
+        }
+    }
+    Vector2::ZERO
+}
+
 fn is_standing_on_platform(body: &mut Gd<CharacterBody2D>, collision_layer: i32) -> bool {
     let Some(collision) = body.get_last_slide_collision() else {
         return false;
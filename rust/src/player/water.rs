@@ -368,6 +368,13 @@ pub fn input_without_regular_jump(mut input: MovementInput) -> MovementInput {
     input
 }
 
+/// Without swim-dive, down input keeps the player floating at the surface
+/// and stops them sinking while submerged.
+pub fn input_without_dive(mut input: MovementInput) -> MovementInput {
+    input.vertical_direction = input.vertical_direction.min(0.0);
+    input
+}
+
 fn is_horizontally_inside(player_x: f32, bounds: WaterBounds) -> bool {
     player_x >= bounds.left() && player_x <= bounds.right()
 }
@@ -489,6 +496,29 @@ mod tests {
         assert_eq!(velocity.y, 0.0);
     }
 
+    #[test]
+    fn without_dive_down_input_keeps_player_floating() {
+        let dive = MovementInput {
+            vertical_direction: 1.0,
+            ..Default::default()
+        };
+        let input = input_without_dive(dive);
+
+        assert!(should_snap_to_surface_float(input));
+        assert_eq!(
+            velocity_for_submerged(Vector2::ZERO, input, WaterTuning::default()).y,
+            WaterTuning::default().buoyancy_velocity
+        );
+        assert_eq!(
+            input_without_dive(MovementInput {
+                vertical_direction: -1.0,
+                ..Default::default()
+            })
+            .vertical_direction,
+            -1.0
+        );
+    }
+
     #[test]
     fn overlay_mask_for_surface_covers_body_below_waterline() {
         let overlay = overlay_mask_for_contact(WaterContact::Surface { surface_y: 128.0 }, 124.0)
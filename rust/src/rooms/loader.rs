@@ -6,6 +6,9 @@ use std::{collections::HashMap, fmt};
 
 use crate::core::world::RoomId;
 
+/// Where the LDtk pipeline writes the room scenes the game loads.
+pub(crate) const ROOM_SCENE_PATTERN: &str = "res://pipeline/ldtk/levels/Room_{x}_{y}.scn";
+
 /// Room loader that handles loading and caching room scenes
 ///
 /// Design considerations from spec:
@@ -1,4 +1,5 @@
 mod loader;
 
+pub(crate) use loader::ROOM_SCENE_PATTERN;
 pub(crate) use loader::RoomLoadError;
 pub(crate) use loader::RoomLoader;
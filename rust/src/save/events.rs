@@ -0,0 +1,119 @@
+use godot::prelude::*;
+
+use crate::core::progress::events::{self, ProgressEvent, SubscriptionId};
+
+/// Scene-tree path of the `ProgressEvents` autoload.
+const AUTOLOAD_PATH: &str = "/root/ProgressEvents";
+
+/// Mirrors `core::progress` events as signals. Registered as the
+/// `ProgressEvents` autoload so HUD nodes and GDScript tools can connect to
+/// it instead of polling `SaveApi`.
+#[derive(GodotClass)]
+#[class(base=Node)]
+pub struct ProgressEventBus {
+    base: Base<Node>,
+    subscription: Option<SubscriptionId>,
+}
+
+#[godot_api]
+impl INode for ProgressEventBus {
+    fn init(base: Base<Node>) -> Self {
+        Self {
+            base,
+            subscription: None,
+        }
+    }
+
+    fn ready(&mut self) {
+        let bus = self.to_gd();
+        self.subscription = Some(events::subscribe(move |event| {
+            Self::emit_event(&bus, event);
+        }));
+    }
+
+    fn exit_tree(&mut self) {
+        if let Some(subscription) = self.subscription.take() {
+            events::unsubscribe(subscription);
+        }
+    }
+}
+
+#[godot_api]
+impl ProgressEventBus {
+    /// `kind` is "key", "lock", "star" or "ability_pickup"; `key` uses the
+    /// save-file form.
+    #[signal]
+    pub fn entity_marked(kind: GString, key: GString);
+
+    #[signal]
+    pub fn star_collected(key: GString, star_count: i64);
+
+    /// `ability` is an ability name such as "dash".
+    #[signal]
+    pub fn ability_unlocked(ability: GString);
+
+    #[signal]
+    pub fn room_explored(room: Vector2i);
+
+    #[signal]
+    pub fn checkpoint_saved(slot: i64, room: Vector2i, position: Vector2);
+
+    /// The active slot's progress was replaced; re-read anything cached.
+    #[signal]
+    pub fn slot_loaded(slot: i64);
+
+    /// Slot `to` now holds a copy of `from`; views of `to` should re-read it.
+    #[signal]
+    pub fn slot_copied(from: i64, to: i64);
+
+    /// Views of `slot` should re-read it, now empty.
+    #[signal]
+    pub fn slot_deleted(slot: i64);
+
+    /// Signals are emitted without binding the bus, so handlers may call
+    /// back into it.
+    fn emit_event(bus: &Gd<Self>, event: &ProgressEvent) {
+        match event {
+            ProgressEvent::EntityMarked { kind, key } => {
+                bus.signals().entity_marked().emit(
+                    &GString::from(kind.name()),
+                    &GString::from(&key.to_string()),
+                );
+            }
+            ProgressEvent::StarCollected { key, star_count } => {
+                bus.signals()
+                    .star_collected()
+                    .emit(&GString::from(&key.to_string()), *star_count as i64);
+            }
+            ProgressEvent::AbilityUnlocked(ability) => {
+                bus.signals()
+                    .ability_unlocked()
+                    .emit(&GString::from(ability.name()));
+            }
+            ProgressEvent::RoomExplored(room) => {
+                bus.signals().room_explored().emit(Vector2i::from(*room));
+            }
+            ProgressEvent::CheckpointSaved { slot, snapshot } => {
+                bus.signals().checkpoint_saved().emit(
+                    *slot as i64,
+                    Vector2i::from(snapshot.room),
+                    snapshot.position,
+                );
+            }
+            ProgressEvent::SlotLoaded(slot) => {
+                bus.signals().slot_loaded().emit(*slot as i64);
+            }
+            ProgressEvent::SlotCopied { from, to } => {
+                bus.signals().slot_copied().emit(*from as i64, *to as i64);
+            }
+            ProgressEvent::SlotDeleted(slot) => {
+                bus.signals().slot_deleted().emit(*slot as i64);
+            }
+        }
+    }
+}
+
+/// The autoloaded bus, if the project has it registered.
+pub(crate) fn find_bus(node: &Gd<Node>) -> Option<Gd<ProgressEventBus>> {
+    node.get_node_or_null(AUTOLOAD_PATH)?.try_cast().ok()
+}
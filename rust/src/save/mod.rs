@@ -1,37 +1,73 @@
 use godot::prelude::*;
 
 use crate::core::progress;
-#[cfg(test)]
-use crate::core::progress::PersistentEntityKind;
+use crate::core::progress::abilities::Ability;
+use crate::core::progress::statistics::DeathCause;
+use crate::core::progress::{CommitPolicy, PersistentEntityKind};
 use crate::core::world::RoomId;
+use crate::rooms::{ROOM_SCENE_PATTERN, RoomLoader};
+
+pub mod events;
+mod storage;
+
+pub use storage::{ensure_loaded, recovered_from, write_slot};
 
 pub use crate::core::progress::{
-    DEFAULT_SAVE_SLOT, clear_pending_load, get_star_count, has_save, is_room_explored,
-    list_explored_rooms, mark_room_explored, queue_load,
+    SAVE_SLOT_COUNT, SaveSlot, SlotSummary, active_slot, add_playtime, add_room_time,
+    clear_pending_load, get_star_count, has_save, is_room_explored, list_explored_rooms,
+    most_recent_slot, queue_load, record_death, record_jump, record_room_transition, restart_run,
+    slot_summary, unlocked_abilities,
 };
 
-#[cfg(test)]
-pub fn mark_lock_unlocked(room: RoomId, position: Vector2) {
-    let _marked = progress::mark_entity(PersistentEntityKind::Lock, room, position);
+/// Starts a fresh game in `slot`, discarding whatever was saved there.
+pub fn start_new_game(slot: SaveSlot) {
+    progress::start_new_game(slot);
+    let _removed = storage::remove_slot(slot);
 }
 
-#[cfg(test)]
-pub fn is_lock_unlocked(room: RoomId, position: Vector2) -> bool {
-    progress::has_entity(PersistentEntityKind::Lock, room, position)
+/// Writes the active slot without moving its checkpoint or rotating its
+/// backups. Skipped until the run has reached a checkpoint, so an empty slot
+/// never appears saved.
+pub fn autosave() -> bool {
+    let slot = active_slot();
+    has_save(slot) && storage::autosave_slot(slot)
 }
 
-#[cfg(test)]
-pub fn mark_key_collected(room: RoomId, position: Vector2) {
-    let _marked = progress::mark_entity(PersistentEntityKind::Key, room, position);
+/// The active slot's checkpoint and progress as portable text.
+pub fn export_progress() -> String {
+    progress::export_slot(active_slot())
 }
 
-#[cfg(test)]
-pub fn is_key_collected(room: RoomId, position: Vector2) -> bool {
-    progress::has_entity(PersistentEntityKind::Key, room, position)
+/// Replaces the active slot with exported text and writes it, after checking
+/// that every entity kind and room it names exists in this build.
+pub fn import_progress(text: &str) -> bool {
+    let slot = active_slot();
+    let mut loader = RoomLoader::new(ROOM_SCENE_PATTERN.to_string());
+    match progress::import_slot(slot, text, |room| loader.room_exists(room)) {
+        Ok(()) => {
+            godot_print!("[Save] imported progress into slot {}", slot);
+            storage::write_slot(slot)
+        }
+        Err(error) => {
+            godot_warn!("[Save] rejected imported progress: {}", error);
+            false
+        }
+    }
+}
+
+pub fn copy_slot(from: SaveSlot, to: SaveSlot) -> bool {
+    progress::copy_slot(from, to) && storage::write_slot(to)
 }
 
-pub fn reset_all() {
-    progress::reset_all();
+pub fn delete_slot(slot: SaveSlot) -> bool {
+    progress::delete_slot(slot);
+    storage::remove_slot(slot)
+}
+
+fn slot_index(slot: i64) -> Option<SaveSlot> {
+    usize::try_from(slot)
+        .ok()
+        .filter(|slot| *slot < SAVE_SLOT_COUNT)
 }
 
 #[derive(GodotClass)]
@@ -48,16 +84,78 @@ impl IRefCounted for SaveApi {
     }
 }
 
+impl SaveApi {
+    pub fn slot_summary(&self, slot: SaveSlot) -> SlotSummary {
+        slot_summary(slot)
+    }
+}
+
 #[godot_api]
 impl SaveApi {
+    #[func]
+    pub fn get_slot_count(&self) -> i64 {
+        SAVE_SLOT_COUNT as i64
+    }
+
     #[func]
     pub fn has_save(&self, slot: i64) -> bool {
-        has_save(slot as usize)
+        slot_index(slot).is_some_and(has_save)
     }
 
     #[func]
     pub fn queue_load(&self, slot: i64) -> bool {
-        queue_load(slot as usize)
+        slot_index(slot).is_some_and(queue_load)
+    }
+
+    /// Returns the slot to resume from the main menu, or -1 without saves.
+    #[func]
+    pub fn get_most_recent_slot(&self) -> i64 {
+        most_recent_slot().map_or(-1, |slot| slot as i64)
+    }
+
+    /// The active slot as text a tester can paste into a bug report.
+    #[func]
+    pub fn export_progress(&self) -> GString {
+        GString::from(&export_progress())
+    }
+
+    /// Loads text from `export_progress` into the active slot. Rejected text,
+    /// e.g. naming unknown entity kinds or missing rooms, changes nothing.
+    #[func]
+    pub fn import_progress(&self, text: GString) -> bool {
+        import_progress(&text.to_string())
+    }
+
+    /// Path of the backup `slot` was recovered from at startup, or an empty
+    /// string when its slot file loaded normally.
+    #[func]
+    pub fn get_recovered_file(&self, slot: i64) -> GString {
+        slot_index(slot)
+            .and_then(recovered_from)
+            .map(|path| GString::from(&path))
+            .unwrap_or_default()
+    }
+
+    #[func]
+    pub fn start_new_game(&self, slot: i64) -> bool {
+        let Some(slot) = slot_index(slot) else {
+            return false;
+        };
+        start_new_game(slot);
+        true
+    }
+
+    #[func]
+    pub fn copy_slot(&self, from: i64, to: i64) -> bool {
+        match (slot_index(from), slot_index(to)) {
+            (Some(from), Some(to)) => copy_slot(from, to),
+            _ => false,
+        }
+    }
+
+    #[func]
+    pub fn delete_slot(&self, slot: i64) -> bool {
+        slot_index(slot).is_some_and(delete_slot)
     }
 
     #[func]
@@ -65,6 +163,105 @@ impl SaveApi {
         clear_pending_load();
     }
 
+    /// Chooses whether `kind` ("key", "lock", "star" or "ability_pickup")
+    /// waits for the next checkpoint before it is kept. Returns false for
+    /// unknown kinds.
+    #[func]
+    pub fn set_transactional(&self, kind: GString, transactional: bool) -> bool {
+        let Some(kind) = PersistentEntityKind::from_name(&kind.to_string()) else {
+            return false;
+        };
+        let policy = if transactional {
+            CommitPolicy::Transactional
+        } else {
+            CommitPolicy::Immediate
+        };
+        progress::set_commit_policy(kind, policy);
+        true
+    }
+
+    #[func]
+    pub fn is_transactional(&self, kind: GString) -> bool {
+        PersistentEntityKind::from_name(&kind.to_string())
+            .is_some_and(|kind| progress::commit_policy(kind) == CommitPolicy::Transactional)
+    }
+
+    /// Total playtime of the active slot, in seconds.
+    #[func]
+    pub fn get_playtime_seconds(&self) -> f64 {
+        progress::playtime_seconds()
+    }
+
+    #[func]
+    pub fn get_jump_count(&self) -> i64 {
+        progress::statistics().jumps() as i64
+    }
+
+    #[func]
+    pub fn get_room_transition_count(&self) -> i64 {
+        progress::statistics().room_transitions() as i64
+    }
+
+    #[func]
+    pub fn get_death_count(&self) -> i64 {
+        progress::statistics().death_count() as i64
+    }
+
+    #[func]
+    pub fn get_deaths_in_room(&self, room: Vector2i) -> i64 {
+        progress::statistics().deaths_in_room(RoomId::from(room)) as i64
+    }
+
+    /// `cause` is "hazard" or "respawn"; unknown causes count zero.
+    #[func]
+    pub fn get_deaths_by_cause(&self, cause: GString) -> i64 {
+        DeathCause::from_name(&cause.to_string()).map_or(0, |cause| {
+            progress::statistics().deaths_by_cause(cause) as i64
+        })
+    }
+
+    #[func]
+    pub fn get_timed_rooms(&self) -> Array<Vector2i> {
+        let mut rooms = Array::new();
+        for (room, _) in progress::statistics().room_times() {
+            rooms.push(Vector2i::from(room));
+        }
+        rooms
+    }
+
+    /// Seconds spent in `room` across the active slot's playtime.
+    #[func]
+    pub fn get_room_time(&self, room: Vector2i) -> f64 {
+        progress::statistics().room_seconds(RoomId::from(room))
+    }
+
+    /// Playtime at which each collected star was picked up, earliest first.
+    #[func]
+    pub fn get_star_collect_times(&self) -> PackedFloat64Array {
+        let mut times: Vec<f64> = progress::statistics()
+            .star_collect_times()
+            .map(|(_, seconds)| seconds)
+            .collect();
+        times.sort_by(f64::total_cmp);
+        times.into_iter().collect()
+    }
+
+    /// Whether the active slot has unlocked `ability`, e.g. "wall_jump".
+    /// Unknown names are never unlocked.
+    #[func]
+    pub fn has_ability(&self, ability: GString) -> bool {
+        Ability::from_name(&ability.to_string())
+            .is_some_and(|ability| progress::unlocked_abilities().contains(ability))
+    }
+
+    #[func]
+    pub fn get_unlocked_abilities(&self) -> PackedStringArray {
+        progress::unlocked_abilities()
+            .iter()
+            .map(|ability| GString::from(ability.name()))
+            .collect()
+    }
+
     #[func]
     pub fn get_explored_rooms(&self) -> Array<Vector2i> {
         let mut rooms = Array::new();
@@ -83,29 +280,42 @@ impl SaveApi {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::progress::{DEFAULT_SAVE_SLOT, ProgressContext};
 
     fn room(x: i32, y: i32) -> RoomId {
         RoomId::new(x, y)
     }
 
     #[test]
-    fn reset_all_clears_everything() {
-        progress::save_checkpoint(DEFAULT_SAVE_SLOT, room(1, 2), Vector2::new(10.0, 20.0));
-        assert!(progress::has_save(DEFAULT_SAVE_SLOT));
-
-        mark_key_collected(room(1, 2), Vector2::new(30.0, 40.0));
-        mark_lock_unlocked(room(1, 2), Vector2::new(50.0, 60.0));
-        assert!(is_key_collected(room(1, 2), Vector2::new(30.0, 40.0)));
-        assert!(is_lock_unlocked(room(1, 2), Vector2::new(50.0, 60.0)));
+    fn slot_index_rejects_out_of_range_slots() {
+        assert_eq!(slot_index(0), Some(0));
+        assert_eq!(
+            slot_index(SAVE_SLOT_COUNT as i64 - 1),
+            Some(SAVE_SLOT_COUNT - 1)
+        );
+        assert_eq!(slot_index(SAVE_SLOT_COUNT as i64), None);
+        assert_eq!(slot_index(-1), None);
+    }
 
-        mark_room_explored(room(2, 3));
-        assert!(is_room_explored(room(2, 3)));
+    #[test]
+    fn new_game_clears_checkpoint_marks_and_rooms() {
+        let progress = ProgressContext::default();
+        let key = progress::make_legacy_key(room(1, 2), Vector2::new(30.0, 40.0));
+        let lock = progress::make_legacy_key(room(1, 2), Vector2::new(50.0, 60.0));
 
-        reset_all();
+        progress.write(|repository| {
+            repository.save_checkpoint(DEFAULT_SAVE_SLOT, room(1, 2), Vector2::ZERO, None);
+            repository.mark_entity_key(PersistentEntityKind::Key, key.clone());
+            repository.mark_entity_key(PersistentEntityKind::Lock, lock.clone());
+            repository.mark_room_explored(room(2, 3));
+            repository.start_new_game(DEFAULT_SAVE_SLOT);
+        });
 
-        assert!(!progress::has_save(DEFAULT_SAVE_SLOT));
-        assert!(!is_key_collected(room(1, 2), Vector2::new(30.0, 40.0)));
-        assert!(!is_lock_unlocked(room(1, 2), Vector2::new(50.0, 60.0)));
-        assert!(!is_room_explored(room(2, 3)));
+        progress.read(|repository| {
+            assert!(!repository.has_save(DEFAULT_SAVE_SLOT));
+            assert!(!repository.has_entity_key(PersistentEntityKind::Key, &key));
+            assert!(!repository.has_entity_key(PersistentEntityKind::Lock, &lock));
+            assert!(!repository.is_room_explored(room(2, 3)));
+        });
     }
 }
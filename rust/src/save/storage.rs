@@ -0,0 +1,314 @@
+//! On-disk persistence for save slots.
+//! Each slot is written as a versioned, checksummed text file under
+//! `user://`, with rolling backups next to it.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use godot::classes::file_access::ModeFlags;
+use godot::classes::{DirAccess, FileAccess, Time};
+use godot::global::Error;
+use godot::prelude::*;
+
+use crate::core::progress::checksum::{self, ChecksumStatus};
+use crate::core::progress::legacy_iids::LegacyIidTable;
+use crate::core::progress::{self, SAVE_SLOT_COUNT, SaveSlot};
+
+const SAVE_PATH_PATTERN: &str = "user://save_slot_{slot}.txt";
+const BACKUP_PATH_PATTERN: &str = "user://save_slot_{slot}.bak{backup}.txt";
+const TEMP_SUFFIX: &str = ".tmp";
+/// Older copies kept per slot; `bak1` is the newest.
+const BACKUP_COUNT: usize = 3;
+/// Generated by `cargo xtask ldtk-iids`; maps legacy keys to LDtk IIDs.
+const LEGACY_IID_TABLE_PATH: &str = "res://pipeline/ldtk/legacy_iids.txt";
+
+thread_local! {
+    static LOADED_FROM_DISK: Cell<bool> = const { Cell::new(false) };
+    static RECOVERED_FROM: RefCell<HashMap<SaveSlot, String>> = RefCell::new(HashMap::new());
+}
+
+fn slot_path(slot: SaveSlot) -> String {
+    SAVE_PATH_PATTERN.replace("{slot}", &slot.to_string())
+}
+
+fn temp_path(slot: SaveSlot) -> String {
+    slot_path(slot) + TEMP_SUFFIX
+}
+
+fn backup_path(slot: SaveSlot, backup: usize) -> String {
+    BACKUP_PATH_PATTERN
+        .replace("{slot}", &slot.to_string())
+        .replace("{backup}", &backup.to_string())
+}
+
+/// Every file that may hold `slot`, newest first.
+fn candidate_paths(slot: SaveSlot) -> Vec<String> {
+    let mut paths = vec![temp_path(slot), slot_path(slot)];
+    paths.extend((1..=BACKUP_COUNT).map(|backup| backup_path(slot, backup)));
+    paths
+}
+
+/// Loads save files once per process. Later calls keep the in-memory state,
+/// which is newer than disk whenever the game scene reloads after a death.
+pub fn ensure_loaded() {
+    if LOADED_FROM_DISK.replace(true) {
+        return;
+    }
+
+    let legacy_iids = load_legacy_iid_table();
+    for slot in 0..SAVE_SLOT_COUNT {
+        let _loaded = load_slot(slot, legacy_iids.as_ref());
+    }
+}
+
+fn load_legacy_iid_table() -> Option<LegacyIidTable> {
+    if !FileAccess::file_exists(LEGACY_IID_TABLE_PATH) {
+        godot_warn!(
+            "[Save] {} not found - legacy entity keys stay unresolved",
+            LEGACY_IID_TABLE_PATH
+        );
+        return None;
+    }
+
+    let text = FileAccess::get_file_as_string(LEGACY_IID_TABLE_PATH).to_string();
+    match LegacyIidTable::parse(&text) {
+        Ok(table) => {
+            godot_print!(
+                "[Save] loaded {} legacy IID entries from {}",
+                table.entry_count(),
+                LEGACY_IID_TABLE_PATH
+            );
+            Some(table)
+        }
+        Err(error) => {
+            godot_error!("[Save] failed to load {}: {}", LEGACY_IID_TABLE_PATH, error);
+            None
+        }
+    }
+}
+
+/// Loads the newest intact file for `slot`: an interrupted write, the slot
+/// file, then its backups. A slot file without a checksum, written by an
+/// older build, is only used when no checksummed file is intact; older
+/// builds wrote nothing else, so any other file missing its trailer was cut
+/// off mid-write.
+pub fn load_slot(slot: SaveSlot, legacy_iids: Option<&LegacyIidTable>) -> bool {
+    let legacy_path = slot_path(slot);
+    let files: Vec<(String, String)> = candidate_paths(slot)
+        .into_iter()
+        .filter(|path| FileAccess::file_exists(path.as_str()))
+        .map(|path| {
+            let text = FileAccess::get_file_as_string(path.as_str()).to_string();
+            (path, text)
+        })
+        .collect();
+    if files.is_empty() {
+        return false;
+    }
+
+    for require_checksum in [true, false] {
+        for (path, text) in &files {
+            let body = match checksum::verify(text) {
+                ChecksumStatus::Valid(body) if require_checksum => body,
+                ChecksumStatus::Missing(body) if !require_checksum && *path == legacy_path => body,
+                ChecksumStatus::Mismatch if require_checksum => {
+                    godot_warn!("[Save] checksum mismatch in {}", path);
+                    continue;
+                }
+                _ => continue,
+            };
+            if restore_from(slot, path, body, legacy_iids) {
+                return true;
+            }
+        }
+    }
+
+    godot_error!("[Save] slot {}: no intact save file found", slot);
+    false
+}
+
+fn restore_from(
+    slot: SaveSlot,
+    path: &str,
+    text: &str,
+    legacy_iids: Option<&LegacyIidTable>,
+) -> bool {
+    match progress::restore_slot(slot, text, legacy_iids) {
+        Ok(report) => {
+            godot_print!("[Save] loaded slot {} from {}", slot, path);
+            if path != slot_path(slot) {
+                godot_warn!("[Save] slot {}: recovered from {}", slot, path);
+                RECOVERED_FROM.with_borrow_mut(|recovered| {
+                    recovered.insert(slot, path.to_string());
+                });
+            }
+            if report.resolved > 0 {
+                godot_print!(
+                    "[Save] slot {}: resolved {} legacy keys to LDtk IIDs",
+                    slot,
+                    report.resolved
+                );
+            }
+            for key in &report.unresolved {
+                godot_warn!("[Save] slot {}: no LDtk IID for legacy key {:?}", slot, key);
+            }
+            true
+        }
+        Err(error) => {
+            godot_error!("[Save] failed to load {}: {}", path, error);
+            false
+        }
+    }
+}
+
+/// The backup or temporary file `slot` was recovered from at startup, if
+/// its slot file was damaged or missing.
+pub fn recovered_from(slot: SaveSlot) -> Option<String> {
+    RECOVERED_FROM.with_borrow(|recovered| recovered.get(&slot).cloned())
+}
+
+/// Writes to a temporary file, rotates the backups, then renames the
+/// temporary file into place so a crash never leaves a half-written slot.
+/// The slot only counts as played now once the write has succeeded.
+pub fn write_slot(slot: SaveSlot) -> bool {
+    write(slot, true)
+}
+
+/// Like `write_slot` but leaves the backups alone. Autosaves run on every
+/// room load and would otherwise push each older copy out within a few rooms.
+pub fn autosave_slot(slot: SaveSlot) -> bool {
+    write(slot, false)
+}
+
+fn write(slot: SaveSlot, rotate: bool) -> bool {
+    let now = Time::singleton().get_unix_time_from_system() as i64;
+    let text = checksum::seal(&progress::encode_slot_played_at(slot, now));
+
+    let path = slot_path(slot);
+    let temp = temp_path(slot);
+    if !write_file(&temp, &text) {
+        return false;
+    }
+
+    if rotate {
+        rotate_backups(slot);
+    }
+    if !rename(&temp, &path) {
+        return false;
+    }
+    progress::touch_slot(slot, now);
+
+    godot_print!("[Save] wrote slot {} to {}", slot, path);
+    true
+}
+
+fn write_file(path: &str, text: &str) -> bool {
+    let Some(mut file) = FileAccess::open(path, ModeFlags::WRITE) else {
+        godot_error!(
+            "[Save] failed to open {} for writing: {:?}",
+            path,
+            FileAccess::get_open_error()
+        );
+        return false;
+    };
+
+    file.store_string(text);
+    file.flush();
+    let error = file.get_error();
+    file.close();
+
+    if error != Error::OK {
+        godot_error!("[Save] failed to write {}: {:?}", path, error);
+        return false;
+    }
+    true
+}
+
+/// Shifts `bak1..bakN` down by one and moves the slot file into `bak1`. A
+/// slot file that fails its checksum is dropped instead, so it never pushes
+/// an intact backup out.
+fn rotate_backups(slot: SaveSlot) {
+    let path = slot_path(slot);
+    if !FileAccess::file_exists(path.as_str()) {
+        return;
+    }
+
+    let text = FileAccess::get_file_as_string(path.as_str()).to_string();
+    if checksum::verify(&text) == ChecksumStatus::Mismatch {
+        godot_warn!("[Save] discarding damaged {}", path);
+        let _removed = remove(&path);
+        return;
+    }
+
+    let _removed = remove(&backup_path(slot, BACKUP_COUNT));
+    for backup in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(slot, backup);
+        if FileAccess::file_exists(from.as_str()) {
+            let _renamed = rename(&from, &backup_path(slot, backup + 1));
+        }
+    }
+    let _renamed = rename(&path, &backup_path(slot, 1));
+}
+
+/// Removes the slot file, any interrupted write and every backup.
+pub fn remove_slot(slot: SaveSlot) -> bool {
+    let removed = candidate_paths(slot)
+        .iter()
+        .fold(true, |removed, path| remove(path) && removed);
+    RECOVERED_FROM.with_borrow_mut(|recovered| recovered.remove(&slot));
+    if removed {
+        godot_print!("[Save] removed slot {} at {}", slot, slot_path(slot));
+    }
+    removed
+}
+
+fn remove(path: &str) -> bool {
+    if !FileAccess::file_exists(path) {
+        return true;
+    }
+
+    let error = DirAccess::remove_absolute(path);
+    if error != Error::OK {
+        godot_error!("[Save] failed to remove {}: {:?}", path, error);
+        return false;
+    }
+    true
+}
+
+fn rename(from: &str, to: &str) -> bool {
+    // Some platforms refuse to rename over an existing file.
+    if !remove(to) {
+        return false;
+    }
+
+    let error = DirAccess::rename_absolute(from, to);
+    if error != Error::OK {
+        godot_error!("[Save] failed to rename {} to {}: {:?}", from, to, error);
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_path_substitutes_slot_index() {
+        assert_eq!(slot_path(2), "user://save_slot_2.txt");
+    }
+
+    #[test]
+    fn candidates_list_interrupted_write_then_slot_then_backups() {
+        assert_eq!(
+            candidate_paths(1),
+            vec![
+                "user://save_slot_1.txt.tmp",
+                "user://save_slot_1.txt",
+                "user://save_slot_1.bak1.txt",
+                "user://save_slot_1.bak2.txt",
+                "user://save_slot_1.bak3.txt",
+            ]
+        );
+    }
+}
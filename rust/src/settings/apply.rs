@@ -0,0 +1,80 @@
+//! Pushes settings into Godot's audio buses, window and input map.
+
+use godot::classes::display_server::WindowMode as DisplayWindowMode;
+use godot::classes::{AudioServer, DisplayServer, InputEventKey, InputMap};
+use godot::global::{Key, linear_to_db};
+use godot::obj::EngineEnum;
+use godot::prelude::*;
+
+use crate::core::settings::{
+    AudioSettings, KeyBindings, REBINDABLE_ACTIONS, VideoSettings, WindowMode,
+};
+
+pub(super) const BGM_BUS: &str = "BGM";
+pub(super) const SFX_BUS: &str = "SFX";
+
+/// Same as `display/window/size/viewport_*` in `project.godot`.
+const VIEWPORT_SIZE: Vector2i = Vector2i::new(320, 240);
+
+pub(super) fn apply_audio(audio: &AudioSettings) {
+    set_bus(BGM_BUS, audio.bgm_enabled, audio.bgm_volume);
+    set_bus(SFX_BUS, audio.sfx_enabled, audio.sfx_volume);
+}
+
+fn set_bus(name: &str, enabled: bool, volume: f32) {
+    let mut server = AudioServer::singleton();
+    let index = server.get_bus_index(name);
+    if index < 0 {
+        godot_warn!("[Settings] audio bus {} not found", name);
+        return;
+    }
+
+    server.set_bus_mute(index, !enabled);
+    server.set_bus_volume_db(index, linear_to_db(volume as f64) as f32);
+}
+
+pub(super) fn apply_video(video: &VideoSettings) {
+    let mut display = DisplayServer::singleton();
+    match video.window_mode {
+        WindowMode::Fullscreen => display.window_set_mode(DisplayWindowMode::FULLSCREEN),
+        WindowMode::Windowed => {
+            display.window_set_mode(DisplayWindowMode::WINDOWED);
+            let size = VIEWPORT_SIZE * video.window_scale as i32;
+            display.window_set_size(size);
+            let screen = display.screen_get_usable_rect();
+            display.window_set_position(screen.position + (screen.size - size) / 2);
+        }
+    }
+}
+
+/// Restores project defaults, then swaps in the keyboard overrides. Gamepad
+/// events on rebound actions are kept.
+pub(super) fn apply_key_bindings(bindings: &KeyBindings) {
+    let mut input_map = InputMap::singleton();
+    input_map.load_from_project_settings();
+
+    for action in REBINDABLE_ACTIONS {
+        let Some(keys) = bindings.keys(action) else {
+            continue;
+        };
+        if !input_map.has_action(action) {
+            godot_warn!("[Settings] input action {} not found", action);
+            continue;
+        }
+
+        for event in input_map.action_get_events(action).iter_shared() {
+            if event.clone().try_cast::<InputEventKey>().is_ok() {
+                input_map.action_erase_event(action, &event);
+            }
+        }
+        for &code in keys {
+            let Some(key) = i32::try_from(code).ok().and_then(Key::try_from_ord) else {
+                godot_warn!("[Settings] unknown keycode {} for {}", code, action);
+                continue;
+            };
+            let mut event = InputEventKey::new_gd();
+            event.set_physical_keycode(key);
+            input_map.action_add_event(action, &event);
+        }
+    }
+}
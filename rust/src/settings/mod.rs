@@ -0,0 +1,220 @@
+//! Player settings: audio, video, key bindings and accessibility toggles.
+//! Kept apart from `save`; settings are shared by every save slot.
+
+use std::cell::{Cell, RefCell};
+
+use godot::prelude::*;
+
+use crate::core::settings::{self as core_settings, Settings, WindowMode};
+
+mod apply;
+mod storage;
+
+/// Scene-tree path of the `Settings` autoload.
+const AUTOLOAD_PATH: &str = "/root/Settings";
+
+thread_local! {
+    static LOADED_FROM_DISK: Cell<bool> = const { Cell::new(false) };
+    static CURRENT: RefCell<Settings> = RefCell::new(Settings::default());
+}
+
+/// Loads and applies the settings file once per process.
+pub fn ensure_loaded() {
+    if LOADED_FROM_DISK.replace(true) {
+        return;
+    }
+
+    let settings = storage::load();
+    apply_all(&settings);
+    CURRENT.set(settings);
+}
+
+pub fn current() -> Settings {
+    CURRENT.with_borrow(Settings::clone)
+}
+
+/// Changes settings, then applies and writes them if anything changed.
+/// Returns whether anything changed.
+pub fn update(f: impl FnOnce(&mut Settings)) -> bool {
+    let previous = current();
+    let mut settings = previous.clone();
+    f(&mut settings);
+    if settings == previous {
+        return false;
+    }
+
+    if settings.audio != previous.audio {
+        apply::apply_audio(&settings.audio);
+    }
+    if settings.video != previous.video {
+        apply::apply_video(&settings.video);
+    }
+    if settings.key_bindings != previous.key_bindings {
+        apply::apply_key_bindings(&settings.key_bindings);
+    }
+    let _written = storage::write(&settings);
+    CURRENT.set(settings);
+    true
+}
+
+fn apply_all(settings: &Settings) {
+    apply::apply_audio(&settings.audio);
+    apply::apply_video(&settings.video);
+    apply::apply_key_bindings(&settings.key_bindings);
+}
+
+/// Loads settings before the main scene and exposes them to GDScript.
+/// Registered as the `Settings` autoload.
+#[derive(GodotClass)]
+#[class(base=Node)]
+pub struct GameSettings {
+    base: Base<Node>,
+}
+
+#[godot_api]
+impl INode for GameSettings {
+    fn init(base: Base<Node>) -> Self {
+        Self { base }
+    }
+
+    fn ready(&mut self) {
+        ensure_loaded();
+    }
+}
+
+#[godot_api]
+impl GameSettings {
+    /// Emitted after any setting changes through this node.
+    #[signal]
+    pub fn changed();
+
+    #[func]
+    pub fn is_bgm_enabled(&self) -> bool {
+        current().audio.bgm_enabled
+    }
+
+    #[func]
+    pub fn set_bgm_enabled(&mut self, enabled: bool) {
+        self.change(|settings| settings.audio.bgm_enabled = enabled);
+    }
+
+    #[func]
+    pub fn get_bgm_volume(&self) -> f32 {
+        current().audio.bgm_volume
+    }
+
+    #[func]
+    pub fn set_bgm_volume(&mut self, volume: f32) {
+        self.change(|settings| settings.audio.bgm_volume = core_settings::clamp_volume(volume));
+    }
+
+    #[func]
+    pub fn is_sfx_enabled(&self) -> bool {
+        current().audio.sfx_enabled
+    }
+
+    #[func]
+    pub fn set_sfx_enabled(&mut self, enabled: bool) {
+        self.change(|settings| settings.audio.sfx_enabled = enabled);
+    }
+
+    #[func]
+    pub fn get_sfx_volume(&self) -> f32 {
+        current().audio.sfx_volume
+    }
+
+    #[func]
+    pub fn set_sfx_volume(&mut self, volume: f32) {
+        self.change(|settings| settings.audio.sfx_volume = core_settings::clamp_volume(volume));
+    }
+
+    /// "windowed" or "fullscreen".
+    #[func]
+    pub fn get_window_mode(&self) -> GString {
+        GString::from(current().video.window_mode.name())
+    }
+
+    #[func]
+    pub fn set_window_mode(&mut self, mode: GString) -> bool {
+        let Some(mode) = WindowMode::from_name(&mode.to_string()) else {
+            godot_warn!("[Settings] unknown window mode {}", mode);
+            return false;
+        };
+        self.change(|settings| settings.video.window_mode = mode);
+        true
+    }
+
+    #[func]
+    pub fn get_window_scale(&self) -> i64 {
+        current().video.window_scale as i64
+    }
+
+    #[func]
+    pub fn set_window_scale(&mut self, scale: i64) {
+        let scale = core_settings::clamp_window_scale(scale.clamp(0, u32::MAX as i64) as u32);
+        self.change(|settings| settings.video.window_scale = scale);
+    }
+
+    /// Physical keycodes bound to `action`, or an empty array when it uses
+    /// the project default.
+    #[func]
+    pub fn get_action_keys(&self, action: GString) -> PackedInt64Array {
+        current()
+            .key_bindings
+            .keys(&action.to_string())
+            .map(PackedInt64Array::from)
+            .unwrap_or_default()
+    }
+
+    #[func]
+    pub fn bind_action(&mut self, action: GString, keys: PackedInt64Array) -> bool {
+        let mut bound = false;
+        self.change(|settings| {
+            bound = settings
+                .key_bindings
+                .bind(&action.to_string(), keys.as_slice())
+        });
+        if !bound {
+            godot_warn!("[Settings] cannot bind {} to {:?}", action, keys);
+        }
+        bound
+    }
+
+    #[func]
+    pub fn reset_action(&mut self, action: GString) -> bool {
+        let mut reset = false;
+        self.change(|settings| reset = settings.key_bindings.reset(&action.to_string()));
+        reset
+    }
+
+    #[func]
+    pub fn is_full_jump_on_tap(&self) -> bool {
+        current().accessibility.full_jump_on_tap
+    }
+
+    #[func]
+    pub fn set_full_jump_on_tap(&mut self, enabled: bool) {
+        self.change(|settings| settings.accessibility.full_jump_on_tap = enabled);
+    }
+
+    #[func]
+    pub fn is_quick_respawn_disabled(&self) -> bool {
+        current().accessibility.disable_quick_respawn
+    }
+
+    #[func]
+    pub fn set_quick_respawn_disabled(&mut self, disabled: bool) {
+        self.change(|settings| settings.accessibility.disable_quick_respawn = disabled);
+    }
+
+    fn change(&mut self, f: impl FnOnce(&mut Settings)) {
+        if update(f) {
+            self.signals().changed().emit();
+        }
+    }
+}
+
+/// The autoloaded settings node, if the project has it registered.
+pub(crate) fn find_settings(node: &Gd<Node>) -> Option<Gd<GameSettings>> {
+    node.get_node_or_null(AUTOLOAD_PATH)?.try_cast().ok()
+}
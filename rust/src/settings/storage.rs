@@ -0,0 +1,55 @@
+//! On-disk persistence for player settings under `user://`.
+
+use godot::classes::FileAccess;
+use godot::classes::file_access::ModeFlags;
+use godot::global::Error;
+use godot::prelude::*;
+
+use crate::core::settings::Settings;
+
+const SETTINGS_PATH: &str = "user://settings.txt";
+
+/// Reads the settings file, falling back to defaults when it is missing or
+/// unreadable.
+pub fn load() -> Settings {
+    if !FileAccess::file_exists(SETTINGS_PATH) {
+        return Settings::default();
+    }
+
+    let text = FileAccess::get_file_as_string(SETTINGS_PATH).to_string();
+    match Settings::decode(&text) {
+        Ok(settings) => {
+            godot_print!("[Settings] loaded {}", SETTINGS_PATH);
+            settings
+        }
+        Err(error) => {
+            godot_error!(
+                "[Settings] failed to load {}: {} - using defaults",
+                SETTINGS_PATH,
+                error
+            );
+            Settings::default()
+        }
+    }
+}
+
+pub fn write(settings: &Settings) -> bool {
+    let Some(mut file) = FileAccess::open(SETTINGS_PATH, ModeFlags::WRITE) else {
+        godot_error!(
+            "[Settings] failed to open {} for writing: {:?}",
+            SETTINGS_PATH,
+            FileAccess::get_open_error()
+        );
+        return false;
+    };
+
+    file.store_string(settings.encode().as_str());
+    let error = file.get_error();
+    file.close();
+
+    if error != Error::OK {
+        godot_error!("[Settings] failed to write {}: {:?}", SETTINGS_PATH, error);
+        return false;
+    }
+    true
+}
@@ -0,0 +1,68 @@
+use godot::{
+    classes::{Button, Control, DisplayServer, IControl, Input, Label, Os},
+    prelude::*,
+};
+
+use crate::save;
+
+const DEBUG_ACTION: &str = "ui_debug";
+
+/// Developer tools shown with the debug action. Removed from release builds.
+#[derive(GodotClass)]
+#[class(base=Control)]
+pub struct DebugMenu {
+    base: Base<Control>,
+    copy_save_button: OnReady<Gd<Button>>,
+    status_label: OnReady<Gd<Label>>,
+}
+
+#[godot_api]
+impl IControl for DebugMenu {
+    fn init(base: Base<Control>) -> Self {
+        Self {
+            base,
+            copy_save_button: OnReady::from_node("VBoxContainer/CopySaveButton"),
+            status_label: OnReady::from_node("VBoxContainer/StatusLabel"),
+        }
+    }
+
+    fn ready(&mut self) {
+        if !Os::singleton().is_debug_build() {
+            self.base_mut().queue_free();
+            return;
+        }
+
+        self.base_mut().set_visible(false);
+        self.base_mut()
+            .set_process_mode(godot::classes::node::ProcessMode::ALWAYS);
+
+        let debug_menu = self.to_gd();
+        self.copy_save_button
+            .signals()
+            .pressed()
+            .connect_other(&debug_menu, Self::on_copy_save_button_pressed);
+    }
+
+    fn process(&mut self, _delta: f64) {
+        if Input::singleton().is_action_just_pressed(DEBUG_ACTION) {
+            let visible = !self.base().is_visible();
+            self.base_mut().set_visible(visible);
+            self.status_label.set_text("");
+        }
+    }
+}
+
+#[godot_api]
+impl DebugMenu {
+    /// Copy the active slot's progress so a tester can paste it into a ticket
+    #[func]
+    fn on_copy_save_button_pressed(&mut self) {
+        let text = save::export_progress();
+        DisplayServer::singleton().clipboard_set(text.as_str());
+        godot_print!(
+            "[DebugMenu] copied slot {} progress to the clipboard",
+            save::active_slot()
+        );
+        self.status_label.set_text("COPIED");
+    }
+}
@@ -3,7 +3,8 @@ use godot::{
     prelude::*,
 };
 
-use crate::save::{self, DEFAULT_SAVE_SLOT};
+use super::save_slot_menu::SaveSlotMenu;
+use crate::save;
 
 const GAME_SCENE_PATH: &str = "res://game.tscn";
 
@@ -14,6 +15,7 @@ pub struct MainMenu {
     play_button: OnReady<Gd<Button>>,
     continue_button: Option<Gd<Button>>,
     quit_button: OnReady<Gd<Button>>,
+    save_slot_menu: OnReady<Gd<SaveSlotMenu>>,
 }
 
 #[godot_api]
@@ -24,12 +26,16 @@ impl IControl for MainMenu {
             play_button: OnReady::from_node("VBoxContainer/PlayButton"),
             continue_button: None,
             quit_button: OnReady::from_node("VBoxContainer/QuitButton"),
+            save_slot_menu: OnReady::from_node("SaveSlotMenu"),
         }
     }
 
     fn ready(&mut self) {
         godot_print!("[MainMenu] ready");
 
+        // Pull save files from disk before deciding whether Continue is available
+        save::ensure_loaded();
+
         // Locate optional Continue button (Godot wiring handled in Rust)
         self.continue_button = self.find_continue_button();
 
@@ -64,44 +70,50 @@ impl MainMenu {
             .signals()
             .pressed()
             .connect_other(&main_menu, Self::on_quit_button_pressed);
+
+        self.save_slot_menu
+            .signals()
+            .closed()
+            .connect_other(&main_menu, Self::on_save_slot_menu_closed);
     }
 
-    /// Handle play button press - reset state and load the game scene
+    /// Handle play button press - open the save slot picker
     #[func]
     fn on_play_button_pressed(&mut self) {
         godot_print!("[MainMenu] play button pressed");
-
-        // Reset all game state for new game
-        save::reset_all();
-
-        // Load and switch to game scene
-        let mut tree = self.base().get_tree();
-        if let Err(error) = tree.change_scene_to_file(GAME_SCENE_PATH).into_result() {
-            godot_error!("Failed to change scene to {}: {:?}", GAME_SCENE_PATH, error);
-        }
+        self.save_slot_menu.bind_mut().open();
     }
 
-    /// Handle continue button press - queue load and switch to game scene
+    /// Handle continue button press - resume the most recently played slot
     #[func]
     fn on_continue_button_pressed(&mut self) {
-        if save::queue_load(DEFAULT_SAVE_SLOT) {
+        let Some(slot) = save::most_recent_slot() else {
+            godot_warn!("Continue requested but no save data available");
+            return;
+        };
+
+        if save::queue_load(slot) {
             godot_print!(
                 "[MainMenu] continue button pressed - loading save slot {}",
-                DEFAULT_SAVE_SLOT
+                slot
             );
             let mut tree = self.base().get_tree();
             if let Err(error) = tree.change_scene_to_file(GAME_SCENE_PATH).into_result() {
                 godot_error!("Failed to change scene to {}: {:?}", GAME_SCENE_PATH, error);
             }
-        } else {
-            godot_warn!("Continue requested but no save data available");
         }
     }
 
-    /// Expose whether the default save slot has data (for toggling UI state)
+    /// Refresh Continue after slots were copied or deleted in the picker
+    #[func]
+    fn on_save_slot_menu_closed(&mut self) {
+        self.update_continue_button_state();
+    }
+
+    /// Expose whether any save slot has data (for toggling UI state)
     #[func]
     fn has_checkpoint_save(&self) -> bool {
-        save::has_save(DEFAULT_SAVE_SLOT)
+        save::most_recent_slot().is_some()
     }
 
     fn find_continue_button(&self) -> Option<Gd<Button>> {
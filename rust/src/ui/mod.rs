@@ -1,7 +1,10 @@
 /// UI module for managing menu systems and user interface components
+mod debug_menu;
 mod main_menu;
 mod pause_menu;
 mod room_grid_overlay;
+mod save_slot_menu;
+mod save_slot_model;
 mod star_counter;
 mod world_map;
 mod world_map_model;
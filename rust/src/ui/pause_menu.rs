@@ -3,6 +3,7 @@ use godot::{
     prelude::*,
 };
 
+use crate::entity::{persistence, pushable_crate};
 use crate::save;
 
 const MAIN_MENU_SCENE_PATH: &str = "res://ui/main_menu.tscn";
@@ -12,6 +13,7 @@ const MAIN_MENU_SCENE_PATH: &str = "res://ui/main_menu.tscn";
 pub struct PauseMenu {
     base: Base<Control>,
     resume_button: OnReady<Gd<Button>>,
+    reset_crates_button: OnReady<Gd<Button>>,
     quit_button: OnReady<Gd<Button>>,
     star_label: OnReady<Gd<Label>>,
 }
@@ -22,6 +24,7 @@ impl IControl for PauseMenu {
         Self {
             base,
             resume_button: OnReady::from_node("VBoxContainer/ResumeButton"),
+            reset_crates_button: OnReady::from_node("VBoxContainer/ResetCratesButton"),
             quit_button: OnReady::from_node("VBoxContainer/QuitButton"),
             star_label: OnReady::from_node("StarDisplay/HBoxContainer/Label"),
         }
@@ -64,6 +67,11 @@ impl PauseMenu {
             .pressed()
             .connect_other(&pause_menu, Self::on_resume_button_pressed);
 
+        self.reset_crates_button
+            .signals()
+            .pressed()
+            .connect_other(&pause_menu, Self::on_reset_crates_button_pressed);
+
         self.quit_button
             .signals()
             .pressed()
@@ -108,6 +116,14 @@ impl PauseMenu {
         self.toggle_pause();
     }
 
+    /// Put the room's crates back where they started, for when one is stuck
+    #[func]
+    fn on_reset_crates_button_pressed(&mut self) {
+        godot_print!("[PauseMenu] reset crates button pressed");
+        pushable_crate::reset_all_crates(&mut self.base().get_tree());
+        self.toggle_pause();
+    }
+
     /// Handle quit button press - return to main menu
     #[func]
     fn on_quit_button_pressed(&mut self) {
@@ -116,6 +132,7 @@ impl PauseMenu {
         // Unpause before changing scene
         let mut tree = self.base().get_tree();
         tree.set_pause(false);
+        persistence::save_runtime_state(&mut tree);
         if let Err(error) = tree
             .change_scene_to_file(MAIN_MENU_SCENE_PATH)
             .into_result()
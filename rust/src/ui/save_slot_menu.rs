@@ -0,0 +1,280 @@
+use godot::{
+    classes::{
+        Button, ConfirmationDialog, Control, HBoxContainer, IControl, Label, VBoxContainer,
+        control::SizeFlags,
+    },
+    prelude::*,
+};
+
+use super::save_slot_model::{SlotAction, summary_text};
+use crate::save::{SaveApi, SaveSlot};
+
+const GAME_SCENE_PATH: &str = "res://game.tscn";
+const SLOT_FONT_SIZE: i32 = 8;
+
+#[derive(Clone, Copy)]
+enum RowButton {
+    Primary,
+    NewGame,
+    Copy,
+    Delete,
+}
+
+struct SlotRow {
+    summary: Gd<Label>,
+    primary: Gd<Button>,
+    new_game: Gd<Button>,
+    copy: Gd<Button>,
+    delete: Gd<Button>,
+}
+
+/// Lists every save slot with its summary and drives new game, continue,
+/// copy and delete through `SaveApi`.
+#[derive(GodotClass)]
+#[class(base=Control)]
+pub struct SaveSlotMenu {
+    base: Base<Control>,
+    save_api: Gd<SaveApi>,
+    slot_list: OnReady<Gd<VBoxContainer>>,
+    hint_label: OnReady<Gd<Label>>,
+    back_button: OnReady<Gd<Button>>,
+    confirm_dialog: OnReady<Gd<ConfirmationDialog>>,
+    rows: Vec<SlotRow>,
+    /// Source slot while the player picks where to copy it.
+    copy_source: Option<SaveSlot>,
+    pending_action: Option<SlotAction>,
+}
+
+#[godot_api]
+impl IControl for SaveSlotMenu {
+    fn init(base: Base<Control>) -> Self {
+        Self {
+            base,
+            save_api: SaveApi::new_gd(),
+            slot_list: OnReady::from_node("VBoxContainer/SlotList"),
+            hint_label: OnReady::from_node("VBoxContainer/HintLabel"),
+            back_button: OnReady::from_node("VBoxContainer/BackButton"),
+            confirm_dialog: OnReady::from_node("ConfirmDialog"),
+            rows: Vec::new(),
+            copy_source: None,
+            pending_action: None,
+        }
+    }
+
+    fn ready(&mut self) {
+        self.base_mut().set_visible(false);
+        self.build_rows();
+        self.connect_signals();
+    }
+}
+
+#[godot_api]
+impl SaveSlotMenu {
+    /// Emitted when the player backs out of the menu.
+    #[signal]
+    fn closed();
+
+    pub fn open(&mut self) {
+        self.copy_source = None;
+        self.refresh();
+        self.base_mut().set_visible(true);
+    }
+
+    fn build_rows(&mut self) {
+        let menu = self.to_gd();
+        let slot_count = self.save_api.bind().get_slot_count() as SaveSlot;
+
+        for slot in 0..slot_count {
+            let mut entry = VBoxContainer::new_alloc();
+            entry.add_theme_constant_override("separation", 2);
+
+            let mut summary = Label::new_alloc();
+            summary.add_theme_font_size_override("font_size", SLOT_FONT_SIZE);
+            entry.add_child(&summary);
+
+            let mut buttons = HBoxContainer::new_alloc();
+            let slot_row = SlotRow {
+                summary,
+                primary: Self::add_row_button(&mut buttons, &menu, slot, RowButton::Primary),
+                new_game: Self::add_row_button(&mut buttons, &menu, slot, RowButton::NewGame),
+                copy: Self::add_row_button(&mut buttons, &menu, slot, RowButton::Copy),
+                delete: Self::add_row_button(&mut buttons, &menu, slot, RowButton::Delete),
+            };
+            entry.add_child(&buttons);
+
+            self.slot_list.add_child(&entry);
+            self.rows.push(slot_row);
+        }
+    }
+
+    fn add_row_button(
+        row: &mut Gd<HBoxContainer>,
+        menu: &Gd<Self>,
+        slot: SaveSlot,
+        kind: RowButton,
+    ) -> Gd<Button> {
+        let mut button = Button::new_alloc();
+        button.set_h_size_flags(SizeFlags::EXPAND_FILL);
+        button.add_theme_font_size_override("font_size", SLOT_FONT_SIZE);
+
+        let mut menu = menu.clone();
+        button
+            .signals()
+            .pressed()
+            .connect(move || menu.bind_mut().on_row_button_pressed(slot, kind));
+
+        row.add_child(&button);
+        button
+    }
+
+    fn connect_signals(&mut self) {
+        let menu = self.to_gd();
+
+        self.back_button
+            .signals()
+            .pressed()
+            .connect_other(&menu, Self::on_back_button_pressed);
+
+        self.confirm_dialog
+            .signals()
+            .confirmed()
+            .connect_other(&menu, Self::on_confirm_dialog_confirmed);
+
+        self.confirm_dialog
+            .signals()
+            .canceled()
+            .connect_other(&menu, Self::on_confirm_dialog_canceled);
+    }
+
+    fn refresh(&mut self) {
+        let copy_source = self.copy_source;
+        let summaries: Vec<_> = (0..self.rows.len())
+            .map(|slot| self.save_api.bind().slot_summary(slot))
+            .collect();
+
+        for (row, summary) in self.rows.iter_mut().zip(&summaries) {
+            row.summary.set_text(&summary_text(summary));
+
+            match copy_source {
+                Some(source) => {
+                    row.primary.set_text("Copy Here");
+                    row.primary.set_disabled(summary.slot == source);
+                }
+                None => {
+                    row.primary.set_text(if summary.has_save() {
+                        "Continue"
+                    } else {
+                        "New Game"
+                    });
+                    row.primary.set_disabled(false);
+                }
+            }
+
+            let show_slot_actions = copy_source.is_none() && summary.has_save();
+            row.new_game.set_text("New Game");
+            row.new_game.set_visible(show_slot_actions);
+            row.copy.set_text("Copy");
+            row.copy.set_visible(show_slot_actions);
+            row.delete.set_text("Delete");
+            row.delete.set_visible(show_slot_actions);
+        }
+
+        let hint = match copy_source {
+            Some(source) => format!("Choose where to copy slot {}", source + 1),
+            None => "Choose a save slot".to_string(),
+        };
+        self.hint_label.set_text(&hint);
+        self.back_button.set_text(if copy_source.is_some() {
+            "Cancel"
+        } else {
+            "Back"
+        });
+    }
+
+    fn on_row_button_pressed(&mut self, slot: SaveSlot, kind: RowButton) {
+        let has_save = self.save_api.bind().has_save(slot as i64);
+        let action = match (self.copy_source, kind) {
+            (Some(from), _) => SlotAction::Copy { from, to: slot },
+            (None, RowButton::Primary) if has_save => SlotAction::Continue(slot),
+            (None, RowButton::Primary | RowButton::NewGame) => SlotAction::NewGame(slot),
+            (None, RowButton::Copy) => {
+                self.copy_source = Some(slot);
+                self.refresh();
+                return;
+            }
+            (None, RowButton::Delete) => SlotAction::Delete(slot),
+        };
+
+        match action.confirmation_text(has_save) {
+            Some(text) => {
+                self.pending_action = Some(action);
+                self.confirm_dialog.set_text(&text);
+                self.confirm_dialog.popup_centered();
+            }
+            None => self.run_action(action),
+        }
+    }
+
+    fn run_action(&mut self, action: SlotAction) {
+        godot_print!("[SaveSlotMenu] {:?}", action);
+        let api = self.save_api.clone();
+
+        match action {
+            SlotAction::NewGame(slot) => {
+                if api.bind().start_new_game(slot as i64) {
+                    self.change_to_game_scene();
+                }
+            }
+            SlotAction::Continue(slot) => {
+                if api.bind().queue_load(slot as i64) {
+                    self.change_to_game_scene();
+                } else {
+                    godot_warn!("[SaveSlotMenu] slot {} has no save to continue", slot);
+                }
+            }
+            SlotAction::Copy { from, to } => {
+                if !api.bind().copy_slot(from as i64, to as i64) {
+                    godot_warn!("[SaveSlotMenu] failed to copy slot {} to {}", from, to);
+                }
+                self.copy_source = None;
+                self.refresh();
+            }
+            SlotAction::Delete(slot) => {
+                if !api.bind().delete_slot(slot as i64) {
+                    godot_warn!("[SaveSlotMenu] failed to delete slot {}", slot);
+                }
+                self.refresh();
+            }
+        }
+    }
+
+    fn change_to_game_scene(&mut self) {
+        let mut tree = self.base().get_tree();
+        if let Err(error) = tree.change_scene_to_file(GAME_SCENE_PATH).into_result() {
+            godot_error!("Failed to change scene to {}: {:?}", GAME_SCENE_PATH, error);
+        }
+    }
+
+    #[func]
+    fn on_confirm_dialog_confirmed(&mut self) {
+        if let Some(action) = self.pending_action.take() {
+            self.run_action(action);
+        }
+    }
+
+    #[func]
+    fn on_confirm_dialog_canceled(&mut self) {
+        self.pending_action = None;
+    }
+
+    #[func]
+    fn on_back_button_pressed(&mut self) {
+        if self.copy_source.take().is_some() {
+            self.refresh();
+            return;
+        }
+
+        self.base_mut().set_visible(false);
+        self.signals().closed().emit();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::core::progress::{SaveSlot, SlotSummary};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// An action picked in the slot menu, held while its confirmation is open.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlotAction {
+    NewGame(SaveSlot),
+    Continue(SaveSlot),
+    Copy { from: SaveSlot, to: SaveSlot },
+    Delete(SaveSlot),
+}
+
+impl SlotAction {
+    /// Prompt shown before running the action, or `None` when nothing would
+    /// be lost. `target_has_save` refers to the slot the action writes to.
+    pub fn confirmation_text(&self, target_has_save: bool) -> Option<String> {
+        match *self {
+            Self::NewGame(slot) if target_has_save => Some(format!(
+                "Start a new game in slot {}?\nThe existing save will be erased.",
+                slot + 1
+            )),
+            Self::NewGame(_) | Self::Continue(_) => None,
+            Self::Copy { from, to } if target_has_save => Some(format!(
+                "Copy slot {} into slot {}?\nThe existing save in slot {} will be overwritten.",
+                from + 1,
+                to + 1,
+                to + 1
+            )),
+            Self::Copy { from, to } => {
+                Some(format!("Copy slot {} into slot {}?", from + 1, to + 1))
+            }
+            Self::Delete(slot) => {
+                Some(format!("Delete slot {}?\nThis cannot be undone.", slot + 1))
+            }
+        }
+    }
+}
+
+pub fn summary_text(summary: &SlotSummary) -> String {
+    let title = format!("Slot {}", summary.slot + 1);
+    let Some(room) = summary.room else {
+        return format!("{title}\nEmpty");
+    };
+
+    let last_played = summary
+        .last_played_unix
+        .map_or_else(|| "-".to_string(), format_unix_time);
+    format!(
+        "{title}  Room {},{}  Stars {}  Explored {}\nLast played {}  Playtime {}",
+        room.x,
+        room.y,
+        summary.star_count,
+        summary.explored_room_count,
+        last_played,
+        format_playtime(summary.playtime_seconds)
+    )
+}
+
+pub fn format_playtime(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!(
+        "{}:{:02}:{:02}",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60
+    )
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` in UTC.
+pub fn format_unix_time(unix_time: i64) -> String {
+    let days = unix_time.div_euclid(SECONDS_PER_DAY);
+    let seconds_of_day = unix_time.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds_of_day / 3600,
+        (seconds_of_day / 60) % 60
+    )
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::world::RoomId;
+
+    fn summary(room: Option<RoomId>) -> SlotSummary {
+        SlotSummary {
+            slot: 1,
+            room,
+            star_count: 3,
+            explored_room_count: 5,
+            last_played_unix: Some(1_760_623_380),
+            playtime_seconds: 3723.9,
+        }
+    }
+
+    #[test]
+    fn formats_playtime_and_timestamps() {
+        assert_eq!(format_playtime(0.0), "0:00:00");
+        assert_eq!(format_playtime(3723.9), "1:02:03");
+        assert_eq!(format_unix_time(0), "1970-01-01 00:00");
+        assert_eq!(format_unix_time(951_827_640), "2000-02-29 12:34");
+        assert_eq!(format_unix_time(1_760_623_380), "2025-10-16 14:03");
+    }
+
+    #[test]
+    fn summarizes_saved_and_empty_slots() {
+        assert_eq!(summary_text(&summary(None)), "Slot 2\nEmpty");
+        assert_eq!(
+            summary_text(&summary(Some(RoomId::new(2, 1)))),
+            "Slot 2  Room 2,1  Stars 3  Explored 5\n\
+             Last played 2025-10-16 14:03  Playtime 1:02:03"
+        );
+    }
+
+    #[test]
+    fn confirms_only_destructive_actions() {
+        assert_eq!(SlotAction::Continue(0).confirmation_text(true), None);
+        assert_eq!(SlotAction::NewGame(0).confirmation_text(false), None);
+        assert!(SlotAction::NewGame(0).confirmation_text(true).is_some());
+        assert!(SlotAction::Delete(2).confirmation_text(true).is_some());
+        assert_eq!(
+            SlotAction::Copy { from: 0, to: 2 }.confirmation_text(false),
+            Some("Copy slot 1 into slot 3?".to_string())
+        );
+    }
+}
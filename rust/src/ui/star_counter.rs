@@ -2,6 +2,7 @@ use godot::classes::{Control, IControl, Label, Tween, tween};
 use godot::prelude::*;
 
 use crate::save;
+use crate::save::events;
 
 /// How long the counter stays fully visible (seconds).
 const DISPLAY_DURATION: f64 = 1.5;
@@ -39,22 +40,66 @@ impl IControl for StarCounter {
             .set_modulate(Color::from_rgba(1.0, 1.0, 1.0, 0.0));
 
         // Sync with current count without showing
-        self.cached_count = save::get_star_count();
+        self.sync_count();
+        self.connect_progress_events();
+    }
+}
+
+#[godot_api]
+impl StarCounter {
+    fn connect_progress_events(&mut self) {
+        let node = self.to_gd().upcast::<Node>();
+        let Some(bus) = events::find_bus(&node) else {
+            godot_warn!("[StarCounter] ProgressEvents autoload missing; counter will not update");
+            return;
+        };
+
+        let counter = self.to_gd();
+        bus.signals()
+            .star_collected()
+            .connect_other(&counter, Self::on_star_collected);
+        bus.signals()
+            .slot_loaded()
+            .connect_other(&counter, Self::on_slot_loaded);
+        bus.signals()
+            .slot_copied()
+            .connect_other(&counter, Self::on_slot_copied);
+        bus.signals()
+            .slot_deleted()
+            .connect_other(&counter, Self::on_slot_deleted);
+    }
+
+    #[func]
+    fn on_star_collected(&mut self, _key: GString, star_count: i64) {
+        self.cached_count = star_count as usize;
         self.update_display();
+        self.show_briefly();
+    }
+
+    #[func]
+    fn on_slot_loaded(&mut self, _slot: i64) {
+        self.sync_count();
     }
 
-    fn process(&mut self, _delta: f64) {
-        let current = save::get_star_count();
-        if current != self.cached_count {
-            self.cached_count = current;
-            self.update_display();
-            self.show_briefly();
+    #[func]
+    fn on_slot_copied(&mut self, _from: i64, to: i64) {
+        if usize::try_from(to) == Ok(save::active_slot()) {
+            self.sync_count();
         }
     }
-}
 
-#[godot_api]
-impl StarCounter {
+    #[func]
+    fn on_slot_deleted(&mut self, slot: i64) {
+        if usize::try_from(slot) == Ok(save::active_slot()) {
+            self.sync_count();
+        }
+    }
+
+    fn sync_count(&mut self) {
+        self.cached_count = save::get_star_count();
+        self.update_display();
+    }
+
     fn update_display(&mut self) {
         self.label.set_text(&self.cached_count.to_string());
     }
@@ -3,8 +3,10 @@ use godot::classes::{Control, IControl, Input, InputEvent, InputEventMouseButton
 use godot::global::MouseButton;
 use godot::prelude::*;
 
+use crate::core::world::RoomId;
 use crate::game::room_manager::GameRoomManager;
 use crate::save;
+use crate::save::events;
 
 use super::world_map_model::WorldMapModel;
 
@@ -53,6 +55,8 @@ impl IControl for WorldMap {
         self.base_mut().set_process(true);
         self.base_mut().set_mouse_filter(MouseFilter::STOP);
         self.last_size = self.base().get_size();
+        self.refresh_explored();
+        self.connect_progress_events();
     }
 
     fn process(&mut self, _delta: f64) {
@@ -133,10 +137,10 @@ impl IControl for WorldMap {
     }
 }
 
+#[godot_api]
 impl WorldMap {
     fn open_map(&mut self) {
         self.base_mut().set_visible(true);
-        self.refresh_explored();
         self.select_current_room();
         self.apply_pause(true);
     }
@@ -161,6 +165,60 @@ impl WorldMap {
         tree.set_pause(false);
     }
 
+    fn connect_progress_events(&mut self) {
+        let node = self.to_gd().upcast::<Node>();
+        let Some(bus) = events::find_bus(&node) else {
+            godot_warn!("[WorldMap] ProgressEvents autoload missing; map will not update");
+            return;
+        };
+
+        let map = self.to_gd();
+        bus.signals()
+            .room_explored()
+            .connect_other(&map, Self::on_room_explored);
+        bus.signals()
+            .slot_loaded()
+            .connect_other(&map, Self::on_slot_loaded);
+        bus.signals()
+            .slot_copied()
+            .connect_other(&map, Self::on_slot_copied);
+        bus.signals()
+            .slot_deleted()
+            .connect_other(&map, Self::on_slot_deleted);
+    }
+
+    #[func]
+    fn on_room_explored(&mut self, room: Vector2i) {
+        let cell_size = self.cell_size;
+        let cell_gap = self.cell_gap;
+        let size = self.base().get_size();
+        if self
+            .model
+            .add_explored(RoomId::from(room), cell_size, cell_gap, size)
+        {
+            self.base_mut().queue_redraw();
+        }
+    }
+
+    #[func]
+    fn on_slot_loaded(&mut self, _slot: i64) {
+        self.refresh_explored();
+    }
+
+    #[func]
+    fn on_slot_copied(&mut self, _from: i64, to: i64) {
+        if usize::try_from(to) == Ok(save::active_slot()) {
+            self.refresh_explored();
+        }
+    }
+
+    #[func]
+    fn on_slot_deleted(&mut self, slot: i64) {
+        if usize::try_from(slot) == Ok(save::active_slot()) {
+            self.refresh_explored();
+        }
+    }
+
     fn refresh_explored(&mut self) {
         self.model.refresh_explored(
             save::list_explored_rooms(),
@@ -59,6 +59,27 @@ impl WorldMapModel {
         self.update_grid_origin(control_size);
     }
 
+    /// Adds one newly explored room, keeping the selection. Returns false if
+    /// the room was already on the map.
+    pub fn add_explored(
+        &mut self,
+        room: RoomId,
+        cell_size: Vector2,
+        cell_gap: Vector2,
+        control_size: Vector2,
+    ) -> bool {
+        if self.explored_set.contains(&room) {
+            return false;
+        }
+
+        let selected_room = self.selected_room;
+        let mut rooms: Vec<RoomId> = self.explored_set.iter().copied().collect();
+        rooms.push(room);
+        self.refresh_explored(rooms, cell_size, cell_gap, control_size);
+        self.selected_room = selected_room;
+        true
+    }
+
     pub fn update_grid_origin(&mut self, control_size: Vector2) {
         if self.grid_size == Vector2::ZERO {
             self.grid_origin = control_size * 0.5;
@@ -180,4 +201,23 @@ mod tests {
 
         assert!(!model.select_room_at(in_gap, cell_size));
     }
+
+    #[test]
+    fn add_explored_extends_grid_and_keeps_selection() {
+        let mut model = WorldMapModel::default();
+        let cell_size = Vector2::new(18.0, 18.0);
+        let cell_gap = Vector2::new(6.0, 6.0);
+        let control_size = Vector2::new(200.0, 200.0);
+        model.refresh_explored(vec![room(0, 1)], cell_size, cell_gap, control_size);
+        model.select_current_room(Some(Vector2i::new(0, 1)));
+
+        assert!(model.add_explored(room(1, 1), cell_size, cell_gap, control_size));
+        assert!(!model.add_explored(room(1, 1), cell_size, cell_gap, control_size));
+
+        assert_eq!(
+            model.explored_rooms(),
+            &[Vector2i::new(0, 1), Vector2i::new(1, 1)]
+        );
+        assert_eq!(model.selected_room(), Some(room(0, 1)));
+    }
 }
@@ -13,6 +13,8 @@ pub enum Command {
     Export(ExportArgs),
     UpdateGdext(UpdateGdextArgs),
     UpdateGodotAddons(UpdateGodotAddonsArgs),
+    LdtkIids(LdtkIidsArgs),
+    JumpReach(JumpReachArgs),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
@@ -86,6 +88,21 @@ pub struct UpdateGodotAddonsArgs {
     pub dry_run: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct LdtkIidsArgs {
+    /// Fail instead of writing when the table is out of date.
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct JumpReachArgs {
+    #[arg(long, value_enum, default_value_t = BuildMode::Debug)]
+    pub build: BuildMode,
+    #[arg(long, default_value = "godot")]
+    pub godot_exe: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,4 +150,27 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn parses_ldtk_iids_check() {
+        let cli = Cli::try_parse_from(["xtask", "ldtk-iids", "--check"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Command::LdtkIids(LdtkIidsArgs { check: true })
+        ));
+    }
+
+    #[test]
+    fn parses_jump_reach_without_rebuilding() {
+        let cli = Cli::try_parse_from(["xtask", "jump-reach", "--build", "none"]).unwrap();
+
+        assert!(matches!(
+            cli.command,
+            Command::JumpReach(JumpReachArgs {
+                build: BuildMode::None,
+                ..
+            })
+        ));
+    }
 }
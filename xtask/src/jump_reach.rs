@@ -0,0 +1,55 @@
+use crate::cli::JumpReachArgs;
+use crate::godot;
+use crate::paths::ProjectPaths;
+use crate::process::{self, Program};
+use crate::run;
+use anyhow::Result;
+use std::path::Path;
+
+/// Prints `Player.get_jump_reach_report()` for the player scene's tuning.
+const REPORT_SCRIPT: &str = "res://player/jump_reach_report.gd";
+
+pub fn report_args(godot_dir: &Path) -> Vec<String> {
+    vec![
+        "--headless".into(),
+        "--path".into(),
+        godot_dir.to_string_lossy().into_owned(),
+        "--script".into(),
+        REPORT_SCRIPT.into(),
+    ]
+}
+
+pub fn execute(paths: &ProjectPaths, args: JumpReachArgs) -> Result<()> {
+    for cargo_args in run::rust_build_args(args.build) {
+        process::run(&Program::new("cargo"), &paths.rust_dir, &cargo_args)?;
+    }
+
+    let godot_exe = godot::resolve_godot_executable(&args.godot_exe)?;
+    run::prepare_godot_project(paths, &godot_exe)?;
+    process::run(
+        &Program::new(godot_exe),
+        &paths.repo_root,
+        &report_args(&paths.godot_dir),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_the_report_script_headless_in_the_project() {
+        let args = report_args(Path::new("repo/godot"));
+
+        assert_eq!(
+            args,
+            vec![
+                "--headless",
+                "--path",
+                "repo/godot",
+                "--script",
+                "res://player/jump_reach_report.gd"
+            ]
+        );
+    }
+}
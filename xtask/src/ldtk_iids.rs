@@ -0,0 +1,292 @@
+use crate::cli::LdtkIidsArgs;
+use crate::paths::ProjectPaths;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+
+const LDTK_RELATIVE_PATH: &str = "pipeline/ldtk/tilemap.ldtk";
+const TABLE_RELATIVE_PATH: &str = "pipeline/ldtk/legacy_iids.txt";
+const TABLE_HEADER: &str = "\
+# Generated by `cargo xtask ldtk-iids` from tilemap.ldtk. Do not edit by hand.
+# <room_x> <room_y> <x> <y> <iid>: legacy save keys resolved to LDtk entity IIDs.
+# Rows for entities that moved are kept so older saves still resolve.
+";
+const ROOM_PREFIX: &str = "Room_";
+
+/// Sorted by room row, room column, then position, like the save files.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct LegacyKey {
+    room_y: i32,
+    room_x: i32,
+    y: i32,
+    x: i32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableEntry {
+    iid: String,
+    identifier: Option<String>,
+}
+
+pub type LegacyIidTable = BTreeMap<LegacyKey, TableEntry>;
+
+pub fn execute(paths: &ProjectPaths, args: LdtkIidsArgs) -> Result<()> {
+    let ldtk_path = paths.godot_dir.join(LDTK_RELATIVE_PATH);
+    let table_path = paths.godot_dir.join(TABLE_RELATIVE_PATH);
+
+    let ldtk_text = fs::read_to_string(&ldtk_path)
+        .with_context(|| format!("failed to read {}", ldtk_path.display()))?;
+    let ldtk: Value = serde_json::from_str(&ldtk_text)
+        .with_context(|| format!("failed to parse {}", ldtk_path.display()))?;
+    let fresh = collect_entries(&ldtk)?;
+
+    let existing_text = if table_path.is_file() {
+        fs::read_to_string(&table_path)
+            .with_context(|| format!("failed to read {}", table_path.display()))?
+    } else {
+        String::new()
+    };
+    let fresh_count = fresh.len();
+    let table = merge(parse_table(&existing_text)?, fresh);
+    let rendered = render_table(&table);
+
+    if rendered == existing_text {
+        println!("{} is up to date", table_path.display());
+        return Ok(());
+    }
+    if args.check {
+        anyhow::bail!(
+            "{} is out of date; run `cargo xtask ldtk-iids`",
+            table_path.display()
+        );
+    }
+
+    fs::write(&table_path, rendered)
+        .with_context(|| format!("failed to write {}", table_path.display()))?;
+    println!(
+        "wrote {} ({} entities in tilemap.ldtk, {} rows total)",
+        table_path.display(),
+        fresh_count,
+        table.len()
+    );
+    Ok(())
+}
+
+/// Collects the legacy key every entity would get at runtime: its room from
+/// the `Room_<x>_<y>` level name and the truncated center of its rectangle.
+pub fn collect_entries(ldtk: &Value) -> Result<LegacyIidTable> {
+    let world_levels = ldtk["worlds"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|world| world["levels"].as_array());
+    let levels = ldtk["levels"]
+        .as_array()
+        .into_iter()
+        .chain(world_levels)
+        .flatten();
+
+    let mut table = LegacyIidTable::new();
+    for level in levels {
+        let name = level["identifier"].as_str().unwrap_or_default();
+        let Some((room_x, room_y)) = parse_room_name(name) else {
+            continue;
+        };
+        let layers = level["layerInstances"].as_array().with_context(|| {
+            format!("level {name} has no layerInstances; external levels are not supported")
+        })?;
+
+        for entity in layers
+            .iter()
+            .filter(|layer| layer["__type"] == "Entities")
+            .filter_map(|layer| layer["entityInstances"].as_array())
+            .flatten()
+        {
+            let (x, y) = entity_center(entity)
+                .with_context(|| format!("entity in {name} is missing px, __pivot or size"))?;
+            let iid = entity["iid"]
+                .as_str()
+                .with_context(|| format!("entity in {name} has no iid"))?;
+            table.insert(
+                LegacyKey {
+                    room_y,
+                    room_x,
+                    y,
+                    x,
+                },
+                TableEntry {
+                    iid: iid.to_string(),
+                    identifier: entity["__identifier"].as_str().map(str::to_string),
+                },
+            );
+        }
+    }
+    Ok(table)
+}
+
+/// Mirrors `get_entity_anchor_position` in `entities_post_import.gd` with a
+/// center anchor, then truncates like `progress::make_legacy_key`.
+fn entity_center(entity: &Value) -> Option<(i32, i32)> {
+    let axis = |index: usize, size_field: &str| -> Option<i32> {
+        let position = entity["px"][index].as_f64()?;
+        let pivot = entity["__pivot"][index].as_f64()?;
+        let size = entity[size_field].as_f64()?;
+        let center = position - pivot * size + size * 0.5;
+        Some(center as f32 as i32)
+    };
+    Some((axis(0, "width")?, axis(1, "height")?))
+}
+
+fn parse_room_name(name: &str) -> Option<(i32, i32)> {
+    let (x, y) = name.strip_prefix(ROOM_PREFIX)?.split_once('_')?;
+    Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+pub fn parse_table(text: &str) -> Result<LegacyIidTable> {
+    let mut table = LegacyIidTable::new();
+    for (index, line) in text.lines().enumerate() {
+        let (content, comment) = match line.split_once('#') {
+            Some((content, comment)) => (content.trim(), Some(comment.trim())),
+            None => (line.trim(), None),
+        };
+        if content.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = content.split_whitespace().collect();
+        let [room_x, room_y, x, y, iid] = fields.as_slice() else {
+            anyhow::bail!("malformed legacy IID row on line {}: {line}", index + 1);
+        };
+        let parse = |field: &str| -> Result<i32> {
+            field
+                .parse()
+                .with_context(|| format!("invalid number on line {}: {line}", index + 1))
+        };
+        table.insert(
+            LegacyKey {
+                room_y: parse(room_y)?,
+                room_x: parse(room_x)?,
+                y: parse(y)?,
+                x: parse(x)?,
+            },
+            TableEntry {
+                iid: iid.to_string(),
+                identifier: comment.filter(|c| !c.is_empty()).map(str::to_string),
+            },
+        );
+    }
+    Ok(table)
+}
+
+/// Fresh rows win; existing rows at positions nothing occupies anymore stay.
+pub fn merge(existing: LegacyIidTable, fresh: LegacyIidTable) -> LegacyIidTable {
+    let mut merged = existing;
+    merged.extend(fresh);
+    merged
+}
+
+pub fn render_table(table: &LegacyIidTable) -> String {
+    let mut out = TABLE_HEADER.to_string();
+    for (key, entry) in table {
+        out.push_str(&format!(
+            "{} {} {} {} {}",
+            key.room_x, key.room_y, key.x, key.y, entry.iid
+        ));
+        if let Some(identifier) = &entry.identifier {
+            out.push_str(&format!(" # {identifier}"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entity(identifier: &str, iid: &str, px: [i64; 2], size: [i64; 2]) -> Value {
+        json!({
+            "__identifier": identifier,
+            "__pivot": [0, 0],
+            "iid": iid,
+            "px": px,
+            "width": size[0],
+            "height": size[1],
+        })
+    }
+
+    fn ldtk() -> Value {
+        json!({
+            "levels": [
+                {
+                    "identifier": "Room_0_1",
+                    "layerInstances": [
+                        { "__type": "Tiles", "entityInstances": [] },
+                        {
+                            "__type": "Entities",
+                            "entityInstances": [
+                                entity("PlainLock", "lock-iid", [288, 200], [32, 32]),
+                                entity("Checkpoint", "checkpoint-iid", [40, 93], [16, 21]),
+                            ],
+                        },
+                    ],
+                },
+                { "identifier": "Title", "layerInstances": null },
+            ],
+        })
+    }
+
+    fn key(room_x: i32, room_y: i32, x: i32, y: i32) -> LegacyKey {
+        LegacyKey {
+            room_y,
+            room_x,
+            y,
+            x,
+        }
+    }
+
+    #[test]
+    fn collects_truncated_entity_centers_per_room() {
+        let table = collect_entries(&ldtk()).unwrap();
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table[&key(0, 1, 304, 216)].iid, "lock-iid");
+        assert_eq!(table[&key(0, 1, 48, 103)].iid, "checkpoint-iid");
+    }
+
+    #[test]
+    fn rejects_levels_without_layer_instances() {
+        let ldtk = json!({ "levels": [{ "identifier": "Room_0_0", "layerInstances": null }] });
+
+        assert!(collect_entries(&ldtk).is_err());
+    }
+
+    #[test]
+    fn render_and_parse_roundtrip() {
+        let table = collect_entries(&ldtk()).unwrap();
+        let rendered = render_table(&table);
+
+        assert!(rendered.ends_with(
+            "0 1 48 103 checkpoint-iid # Checkpoint\n0 1 304 216 lock-iid # PlainLock\n"
+        ));
+        assert_eq!(parse_table(&rendered).unwrap(), table);
+    }
+
+    #[test]
+    fn merge_keeps_rows_for_moved_entities() {
+        let existing = parse_table("0 1 100 100 lock-iid # PlainLock\n").unwrap();
+        let merged = merge(existing, collect_entries(&ldtk()).unwrap());
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[&key(0, 1, 100, 100)].iid, "lock-iid");
+        assert_eq!(merged[&key(0, 1, 304, 216)].iid, "lock-iid");
+    }
+
+    #[test]
+    fn parse_table_rejects_malformed_rows() {
+        assert!(parse_table("0 1 x 2 iid\n").is_err());
+        assert!(parse_table("0 1 2 iid\n").is_err());
+    }
+}
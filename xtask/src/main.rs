@@ -1,6 +1,8 @@
 mod cli;
 mod export;
 mod godot;
+mod jump_reach;
+mod ldtk_iids;
 mod paths;
 mod process;
 mod run;
@@ -20,5 +22,7 @@ fn main() -> Result<()> {
         Command::Export(args) => export::execute(&paths, args),
         Command::UpdateGdext(args) => update_gdext::execute(&paths, args),
         Command::UpdateGodotAddons(args) => update_godot_addons::execute(&paths, args),
+        Command::LdtkIids(args) => ldtk_iids::execute(&paths, args),
+        Command::JumpReach(args) => jump_reach::execute(&paths, args),
     }
 }
@@ -3,6 +3,7 @@ use crate::godot;
 use crate::paths::ProjectPaths;
 use crate::process::{self, Program};
 use anyhow::Result;
+use std::path::Path;
 
 pub fn rust_build_args(build: BuildMode) -> Vec<Vec<String>> {
     match build {
@@ -36,10 +37,24 @@ pub fn execute(paths: &ProjectPaths, args: RunArgs) -> Result<()> {
     }
 
     let godot_exe = godot::resolve_godot_executable(&args.godot_exe)?;
+    prepare_godot_project(paths, &godot_exe)?;
+
+    let mut launch_args = godot_launch_args(&args);
+    for value in &mut launch_args {
+        if value == "__GODOT_DIR__" {
+            *value = paths.godot_dir.to_string_lossy().into_owned();
+        }
+    }
+    process::run(&Program::new(godot_exe), &paths.repo_root, &launch_args)
+}
+
+/// Imports the project when its cache is empty, so the extension and
+/// imported resources are there before Godot launches.
+pub fn prepare_godot_project(paths: &ProjectPaths, godot_exe: &Path) -> Result<()> {
     godot::normalize_extension_list(&paths.godot_dir)?;
     if godot::import_needed(&paths.godot_dir) {
         process::run(
-            &Program::new(godot_exe.clone()),
+            &Program::new(godot_exe),
             &paths.repo_root,
             &[
                 "--path".into(),
@@ -50,14 +65,7 @@ pub fn execute(paths: &ProjectPaths, args: RunArgs) -> Result<()> {
         )?;
         godot::normalize_extension_list(&paths.godot_dir)?;
     }
-
-    let mut launch_args = godot_launch_args(&args);
-    for value in &mut launch_args {
-        if value == "__GODOT_DIR__" {
-            *value = paths.godot_dir.to_string_lossy().into_owned();
-        }
-    }
-    process::run(&Program::new(godot_exe), &paths.repo_root, &launch_args)
+    Ok(())
 }
 
 #[cfg(test)]
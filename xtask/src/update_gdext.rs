@@ -7,13 +7,9 @@ use toml_edit::{DocumentMut, value};
 
 pub fn github_api_commit_url(repo_url: &str, branch: &str) -> Option<String> {
     let trimmed = repo_url.trim_end_matches('/');
-    let path = if let Some(rest) = trimmed.strip_prefix("https://github.com/") {
-        rest
-    } else if let Some(rest) = trimmed.strip_prefix("git@github.com:") {
-        rest
-    } else {
-        return None;
-    };
+    let path = trimmed
+        .strip_prefix("https://github.com/")
+        .or_else(|| trimmed.strip_prefix("git@github.com:"))?;
     let path = path.strip_suffix(".git").unwrap_or(path);
     let (owner, repo) = path.split_once('/')?;
     Some(format!(
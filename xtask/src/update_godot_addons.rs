@@ -173,13 +173,9 @@ fn github_api_zipball_url(repo_url: &str, ref_name: &str) -> Option<String> {
 
 fn github_repo_path(repo_url: &str) -> Option<(&str, &str)> {
     let trimmed = repo_url.trim_end_matches('/');
-    let path = if let Some(rest) = trimmed.strip_prefix("https://github.com/") {
-        rest
-    } else if let Some(rest) = trimmed.strip_prefix("git@github.com:") {
-        rest
-    } else {
-        return None;
-    };
+    let path = trimmed
+        .strip_prefix("https://github.com/")
+        .or_else(|| trimmed.strip_prefix("git@github.com:"))?;
     let path = path.strip_suffix(".git").unwrap_or(path);
     path.split_once('/')
 }